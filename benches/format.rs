@@ -0,0 +1,35 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use zh_num::{fast, fmt_zh_num};
+
+const INPUTS: [u64; 7] = [
+    0, 7, 13, 10086, 2_0003_0405, 1003_0300_0000_0001_0000, u64::MAX,
+];
+
+fn bench_zh_num(c: &mut Criterion) {
+    let mut group = c.benchmark_group("zh_num");
+    let mut s = String::new();
+    group.bench_function("fmt", |b| b.iter(|| {
+        for n in INPUTS {
+            s.clear();
+            fmt_zh_num(black_box(n), &mut s).unwrap();
+            black_box(&s);
+        }
+    }));
+    group.bench_function("push", |b| b.iter(|| {
+        for n in INPUTS {
+            s.clear();
+            fast::push_zh_num(black_box(n), &mut s);
+            black_box(&s);
+        }
+    }));
+    let mut buf = [0; fast::MAX_LEN];
+    group.bench_function("write", |b| b.iter(|| {
+        for n in INPUTS {
+            black_box(fast::write_zh_num(black_box(n), &mut buf));
+        }
+    }));
+    group.finish();
+}
+
+criterion_group!(benches, bench_zh_num);
+criterion_main!(benches);
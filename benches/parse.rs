@@ -0,0 +1,44 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use zh_num::{fast, parser, to_zh_num, to_zh_num_upper};
+
+fn inputs() -> Vec<String> {
+    [0, 7, 13, 10086, 2_0003_0405, 1003_0300_0000_0001_0000, u64::MAX]
+        .into_iter()
+        .flat_map(|n| [to_zh_num(n), to_zh_num_upper(n), n.to_string()])
+        .collect()
+}
+
+fn bench_number(c: &mut Criterion) {
+    let inputs = inputs();
+    let mut group = c.benchmark_group("number");
+    group.bench_function("peg", |b| b.iter(|| {
+        for s in &inputs {
+            black_box(parser::number(black_box(s)).ok());
+        }
+    }));
+    group.bench_function("fast", |b| b.iter(|| {
+        for s in &inputs {
+            black_box(fast::number(black_box(s)));
+        }
+    }));
+    group.finish();
+}
+
+fn bench_hard_number(c: &mut Criterion) {
+    let inputs = ["一零零八六", "幺三八幺二三四五六七八", "二〇二四"];
+    let mut group = c.benchmark_group("hard_number");
+    group.bench_function("peg", |b| b.iter(|| {
+        for s in inputs {
+            black_box(parser::hard_number(black_box(s)).ok());
+        }
+    }));
+    group.bench_function("fast", |b| b.iter(|| {
+        for s in inputs {
+            black_box(fast::hard_number(black_box(s)));
+        }
+    }));
+    group.finish();
+}
+
+criterion_group!(benches, bench_number, bench_hard_number);
+criterion_main!(benches);
@@ -0,0 +1,17 @@
+use std::env;
+
+/// Generate the header into `OUT_DIR`, the committed `include/zh_num.h`
+/// is only updated when `ZH_NUM_CAPI_UPDATE_HEADER` is set,
+/// and `tests/c.rs` checks they are the same
+fn main() {
+    let dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let out_dir = env::var("OUT_DIR").unwrap();
+    println!("cargo::rerun-if-changed=src/lib.rs");
+    println!("cargo::rerun-if-changed=cbindgen.toml");
+    println!("cargo::rerun-if-env-changed=ZH_NUM_CAPI_UPDATE_HEADER");
+    let bindings = cbindgen::generate(&dir).expect("generate header");
+    bindings.write_to_file(format!("{out_dir}/zh_num.h"));
+    if env::var_os("ZH_NUM_CAPI_UPDATE_HEADER").is_some() {
+        bindings.write_to_file(format!("{dir}/include/zh_num.h"));
+    }
+}
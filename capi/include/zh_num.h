@@ -0,0 +1,93 @@
+#ifndef ZH_NUM_H
+#define ZH_NUM_H
+
+/* Generated by cbindgen from capi/src/lib.rs, do not edit */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * Success
+ */
+#define ZH_NUM_OK 0
+
+/**
+ * Required pointer is null
+ */
+#define ZH_NUM_ERR_NULL -1
+
+/**
+ * Output buffer too small, the required length is written
+ */
+#define ZH_NUM_ERR_BUFFER_TOO_SMALL -2
+
+/**
+ * Input is not valid UTF-8
+ */
+#define ZH_NUM_ERR_UTF8 -3
+
+/**
+ * Input is not a number
+ */
+#define ZH_NUM_ERR_PARSE -4
+
+/**
+ * Buffer size enough for any number, includes the NUL
+ */
+#define ZH_NUM_MAX_LEN 118
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Convert number to zh numbers, like `to_zh_num`
+ *
+ * The byte length of result excludes NUL is written to `len` if not null,
+ * even the buffer too small
+ *
+ * # Safety
+ * - `buf` is null or valid for `cap` bytes writes
+ * - `len` is null or valid for writes
+ */
+int32_t zh_num_to_zh_num(uint64_t num, char *buf, size_t cap, size_t *len);
+
+/**
+ * Convert number to upper zh numbers, like `to_zh_num_upper`
+ *
+ * # Safety
+ * Same as [`zh_num_to_zh_num`]
+ */
+int32_t zh_num_to_zh_num_upper(uint64_t num, char *buf, size_t cap, size_t *len);
+
+/**
+ * Parse zh numbers at the start of `s`, like `parser::number`
+ *
+ * Overflow of `u64` is [`ZH_NUM_ERR_PARSE`]
+ *
+ * The number is written to `out`,
+ * the consumed bytes is written to `consumed` if not null
+ *
+ * # Safety
+ * - `s` is valid for `len` bytes reads
+ * - `out` is valid for writes
+ * - `consumed` is null or valid for writes
+ */
+int32_t zh_num_number(const char *s, size_t len, uint64_t *out, size_t *consumed);
+
+/**
+ * Parse hard zh numbers at the start of `s`, like `parser::hard_number`
+ *
+ * # Safety
+ * Same as [`zh_num_number`]
+ */
+int32_t zh_num_hard_number(const char *s, size_t len, uint64_t *out, size_t *consumed);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* ZH_NUM_H */
@@ -0,0 +1,158 @@
+//! C ABI of [`zh_num`]
+//!
+//! Strings are UTF-8 bytes with explicit length, not need to NUL-terminated.
+//! Output buffers are always NUL-terminated when success,
+//! the written length excludes the NUL.
+//!
+//! Every function returns [`ZH_NUM_OK`] or a negative error code
+
+use std::{ffi::c_char, slice, str};
+
+use zh_num::fast::{self, write_zh_num, write_zh_num_upper, MAX_LEN};
+
+/// Success
+pub const ZH_NUM_OK: i32 = 0;
+/// Required pointer is null
+pub const ZH_NUM_ERR_NULL: i32 = -1;
+/// Output buffer too small, the required length is written
+pub const ZH_NUM_ERR_BUFFER_TOO_SMALL: i32 = -2;
+/// Input is not valid UTF-8
+pub const ZH_NUM_ERR_UTF8: i32 = -3;
+/// Input is not a number
+pub const ZH_NUM_ERR_PARSE: i32 = -4;
+
+/// Buffer size enough for any number, includes the NUL
+pub const ZH_NUM_MAX_LEN: usize = 118;
+const _: () = assert!(ZH_NUM_MAX_LEN == MAX_LEN + 1);
+
+/// Write `s` and NUL into `buf`
+///
+/// # Safety
+/// - `buf` is null or valid for `cap` bytes writes
+/// - `len` is null or valid for writes
+unsafe fn write_out(s: &str, buf: *mut c_char, cap: usize, len: *mut usize) -> i32 {
+    if !len.is_null() {
+        *len = s.len();
+    }
+    if buf.is_null() {
+        return ZH_NUM_ERR_NULL;
+    }
+    if cap <= s.len() {
+        return ZH_NUM_ERR_BUFFER_TOO_SMALL;
+    }
+    let buf = slice::from_raw_parts_mut(buf.cast::<u8>(), cap);
+    buf[..s.len()].copy_from_slice(s.as_bytes());
+    buf[s.len()] = 0;
+    ZH_NUM_OK
+}
+
+/// Read `len` bytes UTF-8 text from `s`
+///
+/// # Safety
+/// - `s` is valid for `len` bytes reads
+unsafe fn read_in<'a>(s: *const c_char, len: usize) -> Result<&'a str, i32> {
+    if s.is_null() {
+        return Err(ZH_NUM_ERR_NULL);
+    }
+    let bytes = slice::from_raw_parts(s.cast::<u8>(), len);
+    str::from_utf8(bytes).map_err(|_| ZH_NUM_ERR_UTF8)
+}
+
+/// Convert number to zh numbers, like `to_zh_num`
+///
+/// The byte length of result excludes NUL is written to `len` if not null,
+/// even the buffer too small
+///
+/// # Safety
+/// - `buf` is null or valid for `cap` bytes writes
+/// - `len` is null or valid for writes
+#[no_mangle]
+pub unsafe extern "C" fn zh_num_to_zh_num(
+    num: u64,
+    buf: *mut c_char,
+    cap: usize,
+    len: *mut usize,
+) -> i32 {
+    write_out(write_zh_num(num, &mut [0; MAX_LEN]), buf, cap, len)
+}
+
+/// Convert number to upper zh numbers, like `to_zh_num_upper`
+///
+/// # Safety
+/// Same as [`zh_num_to_zh_num`]
+#[no_mangle]
+pub unsafe extern "C" fn zh_num_to_zh_num_upper(
+    num: u64,
+    buf: *mut c_char,
+    cap: usize,
+    len: *mut usize,
+) -> i32 {
+    write_out(write_zh_num_upper(num, &mut [0; MAX_LEN]), buf, cap, len)
+}
+
+/// Parse by `f`, it returns the number and consumed bytes
+///
+/// # Safety
+/// Same as [`zh_num_number`]
+unsafe fn parse(
+    f: impl FnOnce(&str) -> Option<(u64, usize)>,
+    s: *const c_char,
+    len: usize,
+    out: *mut u64,
+    consumed: *mut usize,
+) -> i32 {
+    let s = match read_in(s, len) {
+        Ok(s) => s,
+        Err(code) => return code,
+    };
+    if out.is_null() {
+        return ZH_NUM_ERR_NULL;
+    }
+    let Some((num, len)) = f(s) else { return ZH_NUM_ERR_PARSE };
+    *out = num;
+    if !consumed.is_null() {
+        *consumed = len;
+    }
+    ZH_NUM_OK
+}
+
+/// Parse zh numbers at the start of `s`, like `parser::number`
+///
+/// Overflow of `u64` is [`ZH_NUM_ERR_PARSE`]
+///
+/// The number is written to `out`,
+/// the consumed bytes is written to `consumed` if not null
+///
+/// # Safety
+/// - `s` is valid for `len` bytes reads
+/// - `out` is valid for writes
+/// - `consumed` is null or valid for writes
+#[no_mangle]
+pub unsafe extern "C" fn zh_num_number(
+    s: *const c_char,
+    len: usize,
+    out: *mut u64,
+    consumed: *mut usize,
+) -> i32 {
+    parse(|s| {
+        fast::number(s)
+            .map(|(num, rest)| (num, s.len() - rest.len()))
+    }, s, len, out, consumed)
+}
+
+/// Parse hard zh numbers at the start of `s`, like `parser::hard_number`
+///
+/// # Safety
+/// Same as [`zh_num_number`]
+#[no_mangle]
+pub unsafe extern "C" fn zh_num_hard_number(
+    s: *const c_char,
+    len: usize,
+    out: *mut u64,
+    consumed: *mut usize,
+) -> i32 {
+    parse(|s| {
+        fast::hard_number(s)
+            .map(|(num, rest)| (num, s.len() - rest.len()))
+    }, s, len, out, consumed)
+}
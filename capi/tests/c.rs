@@ -0,0 +1,38 @@
+use std::{env, fs, path::PathBuf, process::Command};
+
+/// The committed header is the same as the generated
+#[test]
+fn test_header() {
+    let manifest_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let committed = fs::read_to_string(manifest_dir.join("include/zh_num.h")).unwrap();
+    let generated = include_str!(concat!(env!("OUT_DIR"), "/zh_num.h"));
+    assert!(committed == generated,
+        "include/zh_num.h is outdated, rebuild with ZH_NUM_CAPI_UPDATE_HEADER=1");
+}
+
+/// Build and run `tests/test.c` with the cdylib
+#[test]
+#[cfg(unix)]
+fn test_c() {
+    let manifest_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    // target/<profile>/deps/<test-exe>
+    let exe = env::current_exe().unwrap();
+    let lib_dir = exe.parent().unwrap().parent().unwrap();
+    let out = lib_dir.join("zh_num_capi_test");
+    let cc = env::var("CC").unwrap_or_else(|_| "cc".into());
+
+    let status = Command::new(cc)
+        .arg(manifest_dir.join("tests/test.c"))
+        .arg("-I").arg(manifest_dir.join("include"))
+        .arg("-L").arg(lib_dir)
+        .arg(format!("-Wl,-rpath,{}", lib_dir.display()))
+        .arg("-lzh_num_capi")
+        .arg("-o").arg(&out)
+        .status()
+        .expect("run C compiler");
+    assert!(status.success(), "compile test.c");
+
+    let output = Command::new(&out).output().unwrap();
+    assert!(output.status.success(), "{output:?}");
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "ok\n");
+}
@@ -0,0 +1,70 @@
+#include <assert.h>
+#include <stdio.h>
+#include <string.h>
+
+#include "zh_num.h"
+
+static void test_to_zh_num(void) {
+    char buf[ZH_NUM_MAX_LEN];
+    size_t len = 0;
+
+    assert(zh_num_to_zh_num(10086, buf, sizeof buf, &len) == ZH_NUM_OK);
+    assert(strcmp(buf, "一万零八十六") == 0);
+    assert(len == strlen("一万零八十六"));
+
+    assert(zh_num_to_zh_num_upper(10086, buf, sizeof buf, NULL) == ZH_NUM_OK);
+    assert(strcmp(buf, "壹万零捌拾陆") == 0);
+
+    assert(zh_num_to_zh_num(UINT64_MAX, buf, sizeof buf, &len) == ZH_NUM_OK);
+    assert(len < ZH_NUM_MAX_LEN);
+
+    char small[4];
+    assert(zh_num_to_zh_num(10086, small, sizeof small, &len)
+        == ZH_NUM_ERR_BUFFER_TOO_SMALL);
+    assert(len == strlen("一万零八十六"));
+
+    assert(zh_num_to_zh_num(1, NULL, 0, NULL) == ZH_NUM_ERR_NULL);
+}
+
+static void test_number(void) {
+    const char *s = "一万零十三章";
+    uint64_t num = 0;
+    size_t consumed = 0;
+
+    assert(zh_num_number(s, strlen(s), &num, &consumed) == ZH_NUM_OK);
+    assert(num == 10013);
+    assert(strcmp(s + consumed, "章") == 0);
+
+    /* not need to NUL-terminated */
+    assert(zh_num_number(s, strlen("一万"), &num, &consumed) == ZH_NUM_OK);
+    assert(num == 10000);
+    assert(consumed == strlen("一万"));
+
+    s = "一零零八六章";
+    assert(zh_num_hard_number(s, strlen(s), &num, NULL) == ZH_NUM_OK);
+    assert(num == 10086);
+
+    s = "章";
+    assert(zh_num_number(s, strlen(s), &num, NULL) == ZH_NUM_ERR_PARSE);
+
+    /* overflow */
+    s = "一亿亿亿";
+    assert(zh_num_number(s, strlen(s), &num, NULL) == ZH_NUM_ERR_PARSE);
+    s = "18446744073709551616";
+    assert(zh_num_number(s, strlen(s), &num, NULL) == ZH_NUM_ERR_PARSE);
+    s = "九二三四五六七八九零一二三四五六七八九零";
+    assert(zh_num_hard_number(s, strlen(s), &num, NULL) == ZH_NUM_ERR_PARSE);
+
+    s = "\xff";
+    assert(zh_num_number(s, strlen(s), &num, NULL) == ZH_NUM_ERR_UTF8);
+
+    assert(zh_num_number(NULL, 0, &num, NULL) == ZH_NUM_ERR_NULL);
+    assert(zh_num_number("1", 1, NULL, NULL) == ZH_NUM_ERR_NULL);
+}
+
+int main(void) {
+    test_to_zh_num();
+    test_number();
+    puts("ok");
+    return 0;
+}
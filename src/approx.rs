@@ -0,0 +1,216 @@
+use std::{
+    fmt::{self, Display},
+    ops::RangeInclusive,
+};
+
+use crate::{fmt_padded, place_value, to_zh_num, Number};
+
+/// Qualifier of an [`Approx`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Qualifier {
+    /// `多`, e.g `三千多`
+    More,
+    /// `余`, e.g `三千余`
+    Over,
+    /// `几` after the number, e.g `十几`
+    Odd,
+    /// `来`, e.g `十来`
+    About,
+    /// `左右`, e.g `三千左右`
+    Around,
+    /// `上`, e.g `上万`
+    AtLeast,
+    /// `近`, e.g `近百`
+    Nearly,
+    /// `几` before the unit, e.g `几十`
+    Several,
+    /// `数` before the unit, e.g `数十`
+    Many,
+}
+
+/// Approximate quantity, see [`parser::approx_number`]
+///
+/// [`parser::approx_number`]: crate::parser::approx_number
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Approx {
+    /// The round number, e.g `300000` of `三十多万`, `10` of `几十`
+    pub number: Number,
+    pub qualifier: Qualifier,
+    /// Bounds of the quantity
+    pub range: RangeInclusive<Number>,
+}
+impl Approx {
+    /// Build from the round number and qualifier,
+    /// [`None`] if the number can't be used with the qualifier
+    ///
+    /// | qualifier                  | range                  | e.g  |
+    /// | -------------------------- | ---------------------- | ---- |
+    /// | `More` `Over`              | `n+1 ..= n+u-1`        | 三千多 `3001..=3999` |
+    /// | `Odd`                      | `n+u/10 ..= n+u-1`     | 十几 `11..=19` |
+    /// | `About` `Around`           | `n-n/10 ..= n+n/10`    | 三十来 `27..=33` |
+    /// | `AtLeast`                  | `n ..= 10n-1`          | 上万 `10000..=99999` |
+    /// | `Nearly`                   | `n-n/10 ..= n-1`       | 近百 `90..=99` |
+    /// | `Several` `Many`           | `2n ..= 10n-1`         | 几十 `20..=99` |
+    ///
+    /// `u` is the place value of the lowest non-zero digit,
+    /// `n/10` is at least one, `Nearly` needs `n` at least ten
+    ///
+    /// # Examples
+    /// ```
+    /// # use zh_num::{Approx, Qualifier};
+    /// let approx = Approx::new(3000, Qualifier::More).unwrap();
+    /// assert_eq!(approx.range, 3001..=3999);
+    /// assert!(Approx::new(3001, Qualifier::More).is_none());
+    /// assert!(Approx::new(30, Qualifier::Several).is_none());
+    /// assert!(Approx::new(5, Qualifier::Nearly).is_none());
+    /// ```
+    pub fn new(number: Number, qualifier: Qualifier) -> Option<Self> {
+        use Qualifier::*;
+
+        let n = number;
+        if n == 0 {
+            return None;
+        }
+        let unit = place_value(n);
+        let delta = (n / 10).max(1);
+        let round = unit >= 10;
+        let range = match qualifier {
+            More | Over if round => n + 1..=n.checked_add(unit - 1)?,
+            Odd if round => n + unit / 10..=n.checked_add(unit - 1)?,
+            About if round => n - delta..=n.checked_add(delta)?,
+            Around => n - delta..=n.checked_add(delta)?,
+            AtLeast => n..=n.checked_mul(10)? - 1,
+            Nearly if n >= 10 => n - delta..=n - 1,
+            Several | Many if round && unit == n => {
+                n * 2..=n.checked_mul(10)? - 1
+            },
+            _ => return None,
+        };
+        Some(Self { number, qualifier, range })
+    }
+}
+
+/// Number without the leading `一` if it is a power of ten, e.g `百` `十万`
+fn fmt_round(num: Number, f: &mut dyn fmt::Write) -> fmt::Result {
+    let s = to_zh_num(num);
+    match s.strip_prefix('一') {
+        Some(rest) if num == Number::pow(10, num.ilog10()) => f.write_str(rest),
+        _ => f.write_str(&s),
+    }
+}
+
+/// Put the qualifier before the trailing `万` or `亿`, e.g `三十多万`
+fn fmt_suffixed(num: Number, q: &str, f: &mut dyn fmt::Write) -> fmt::Result {
+    for (w, unit) in [(1_0000_0000, "亿"), (1_0000, "万")] {
+        let k = num / w;
+        if num.is_multiple_of(w) && k >= 10 && (w != 1_0000 || k < 1_0000) {
+            return write!(f, "{}{q}{unit}", to_zh_num(k));
+        }
+    }
+    write!(f, "{}{q}", to_zh_num(num))
+}
+
+fn fmt_approx(approx: &Approx, f: &mut dyn fmt::Write) -> fmt::Result {
+    let num = approx.number;
+    match approx.qualifier {
+        Qualifier::More => fmt_suffixed(num, "多", f),
+        Qualifier::Over => fmt_suffixed(num, "余", f),
+        Qualifier::Odd => fmt_suffixed(num, "几", f),
+        Qualifier::About => fmt_suffixed(num, "来", f),
+        Qualifier::Around => write!(f, "{}左右", to_zh_num(num)),
+        Qualifier::AtLeast => {
+            f.write_str("上")?;
+            fmt_round(num, f)
+        },
+        Qualifier::Nearly => {
+            f.write_str("近")?;
+            fmt_round(num, f)
+        },
+        Qualifier::Several => {
+            f.write_str("几")?;
+            fmt_round(num, f)
+        },
+        Qualifier::Many => {
+            f.write_str("数")?;
+            fmt_round(num, f)
+        },
+    }
+}
+
+impl Display for Approx {
+    /// # Examples
+    /// ```
+    /// # use zh_num::{Approx, Qualifier};
+    /// let approx = |n, q| Approx::new(n, q).unwrap().to_string();
+    /// assert_eq!(approx(30_0000, Qualifier::More), "三十多万");
+    /// assert_eq!(approx(3_0000, Qualifier::More), "三万多");
+    /// assert_eq!(approx(10, Qualifier::Odd), "十几");
+    /// assert_eq!(approx(1_0000, Qualifier::AtLeast), "上万");
+    /// assert_eq!(approx(10_0000, Qualifier::Several), "几十万");
+    /// assert_eq!(approx(3000, Qualifier::Around), "三千左右");
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_padded(f, |w| fmt_approx(self, w))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::approx_number;
+    use Qualifier::*;
+
+    #[test]
+    fn test_approx_number() {
+        let datas = [
+            ("三千多人", 3000, More, 3001..=3999, "人"),
+            ("三千余", 3000, Over, 3001..=3999, ""),
+            ("百余", 100, Over, 101..=199, ""),
+            ("一万二千多", 12000, More, 12001..=12999, ""),
+            ("三十多万", 30_0000, More, 30_0001..=39_9999, ""),
+            ("三千多万", 3000_0000, More, 3000_0001..=3999_9999, ""),
+            ("3000多", 3000, More, 3001..=3999, ""),
+            ("十几个", 10, Odd, 11..=19, "个"),
+            ("三十几", 30, Odd, 31..=39, ""),
+            ("十几万", 10_0000, Odd, 11_0000..=19_9999, ""),
+            ("十来个", 10, About, 9..=11, "个"),
+            ("三十来岁", 30, About, 27..=33, "岁"),
+            ("五左右", 5, Around, 4..=6, ""),
+            ("三千左右", 3000, Around, 2700..=3300, ""),
+            ("上万元", 1_0000, AtLeast, 1_0000..=9_9999, "元"),
+            ("上百万", 100_0000, AtLeast, 100_0000..=999_9999, ""),
+            ("近百", 100, Nearly, 90..=99, ""),
+            ("近三千", 3000, Nearly, 2700..=2999, ""),
+            ("几十", 10, Several, 20..=99, ""),
+            ("几万", 1_0000, Several, 2_0000..=9_9999, ""),
+            ("几十万", 10_0000, Several, 20_0000..=99_9999, ""),
+            ("数百", 100, Many, 200..=999, ""),
+        ];
+        for (src, number, qualifier, range, rest) in datas {
+            let (approx, s) = approx_number(src).unwrap();
+            assert_eq!(approx, Approx { number, qualifier, range }, "{src}");
+            assert_eq!(s, rest, "{src}");
+        }
+        for src in ["三多", "一百零一多", "零多", "几三", "多", "三千", "近", "近一", "近五"] {
+            assert!(approx_number(src).is_err(), "{src}");
+        }
+    }
+
+    #[test]
+    fn test_display() {
+        let qualifiers = [
+            More, Over, Odd, About, Around, AtLeast, Nearly, Several, Many,
+        ];
+        let nums = [
+            5, 10, 30, 100, 3000, 1_0000, 1_2000, 3_0000, 30_0000,
+            3000_0000, 1_0000_0000, 30_0000_0000, 3_0000_0000_0000,
+        ];
+        for q in qualifiers {
+            for num in nums {
+                let Some(approx) = Approx::new(num, q) else { continue };
+                let s = approx.to_string();
+                assert_eq!(approx_number(&s), Ok((approx, "")), "{s}");
+            }
+        }
+    }
+}
@@ -0,0 +1,185 @@
+//! Syntax tree of zh numbers, see [`parser::ast_number`]
+//!
+//! [`parser::number`] parses into these nodes and then takes the value,
+//! the nodes are also used to explain how a number is parsed
+//!
+//! [`parser::ast_number`]: crate::parser::ast_number
+//! [`parser::number`]: crate::parser::number
+
+use std::fmt::{self, Display};
+
+use crate::{fmt_padded, Number};
+
+/// A digit with a unit, e.g `八十` is `8×10`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Term {
+    pub digit: Number,
+    /// `1` `10` `100` or `1000` in a [`KNumber`],
+    /// any power of ten in [`YiNumber::terms`]
+    pub unit: Number,
+}
+
+/// Number below `万`, e.g `一千零八十六`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct KNumber {
+    /// Terms from the highest unit, never empty
+    pub terms: Vec<Term>,
+}
+impl KNumber {
+    pub fn value(&self) -> Number {
+        self.terms.iter()
+            .map(|t| t.digit * t.unit)
+            .sum()
+    }
+}
+
+/// Number below `亿`, e.g `三万零八十六`
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct WanNumber {
+    /// Number before `万`, [`None`] if no `万`
+    pub wan: Option<KNumber>,
+    /// Number after `万` or the whole number if no `万`
+    pub rest: Option<KNumber>,
+}
+impl WanNumber {
+    pub fn value(&self) -> Number {
+        let value = |k: &Option<KNumber>| k.as_ref().map_or(0, KNumber::value);
+        value(&self.wan) * 1_0000 + value(&self.rest)
+    }
+}
+
+/// Number split by `亿`, e.g `三亿零八十六`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct YiNumber {
+    /// Sections from the highest, never empty,
+    /// each section is followed by a `亿` except the last,
+    /// e.g `一亿亿` is `[一, 空, 空]`
+    pub sections: Vec<WanNumber>,
+}
+impl YiNumber {
+    /// [`None`] if out of range
+    pub fn value(&self) -> Option<Number> {
+        self.wide_value()?.try_into().ok()
+    }
+
+    /// Like [`value`](Self::value), but in [`u128`],
+    /// used by [`parser::integer`](crate::parser::integer)
+    pub(crate) fn wide_value(&self) -> Option<u128> {
+        self.sections.iter().try_fold(0u128, |acc, section| {
+            acc.checked_mul(1_0000_0000)?.checked_add(section.value().into())
+        })
+    }
+
+    /// Non-zero terms from the highest unit,
+    /// the units are multiplied by `万` and `亿`
+    ///
+    /// Terms out of range of [`Number`] are skipped
+    ///
+    /// # Examples
+    /// ```
+    /// # use zh_num::{ast::Term, parser::ast_number};
+    /// let (ast, _) = ast_number("三十万零八").unwrap();
+    /// assert_eq!(ast.terms(), [
+    ///     Term { digit: 3, unit: 10_0000 },
+    ///     Term { digit: 8, unit: 1 },
+    /// ]);
+    /// ```
+    pub fn terms(&self) -> Vec<Term> {
+        let mut terms = vec![];
+        let mut yi = Some(1 as Number);
+        for section in self.sections.iter().rev() {
+            for (k, wan) in [(&section.rest, 1), (&section.wan, 1_0000)] {
+                let Some(k) = k else { continue };
+                for term in k.terms.iter().rev().filter(|t| t.digit != 0) {
+                    let unit = yi.and_then(|yi| {
+                        yi.checked_mul(wan)?.checked_mul(term.unit)
+                    });
+                    if let Some(unit) = unit {
+                        terms.push(Term { digit: term.digit, unit });
+                    }
+                }
+            }
+            yi = yi.and_then(|yi| yi.checked_mul(1_0000_0000));
+        }
+        terms.reverse();
+        terms
+    }
+}
+
+impl Display for YiNumber {
+    /// Sum of [`terms`](Self::terms)
+    ///
+    /// # Examples
+    /// ```
+    /// # use zh_num::parser::ast_number;
+    /// let (ast, _) = ast_number("一万零八十六").unwrap();
+    /// assert_eq!(ast.to_string(), "1×10000 + 8×10 + 6");
+    /// let (ast, _) = ast_number("零").unwrap();
+    /// assert_eq!(ast.to_string(), "0");
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_padded(f, |f| {
+            let terms = self.terms();
+            if terms.is_empty() {
+                return f.write_str("0");
+            }
+            for (i, term) in terms.into_iter().enumerate() {
+                if i != 0 {
+                    f.write_str(" + ")?;
+                }
+                match term.unit {
+                    1 => write!(f, "{}", term.digit)?,
+                    unit => write!(f, "{}×{unit}", term.digit)?,
+                }
+            }
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{parser::{ast_number, number}, to_zh_num, to_zh_num_upper};
+
+    #[test]
+    fn test_ast_number() {
+        let datas = [
+            ("一万零八十六章", "1×10000 + 8×10 + 6", "章"),
+            ("十二", "1×10 + 2", ""),
+            ("一十零一", "1×10 + 1", ""),
+            ("三十万零八", "3×100000 + 8", ""),
+            ("两千一", "2×1000 + 1", ""),
+            ("一亿亿", "1×10000000000000000", ""),
+            ("壹佰万零贰", "1×1000000 + 2", ""),
+            ("零万", "0", ""),
+        ];
+        for (src, explained, rest) in datas {
+            let (ast, s) = ast_number(src).unwrap();
+            assert_eq!(ast.to_string(), explained, "{src}");
+            assert_eq!(s, rest, "{src}");
+        }
+        for src in ["章", "万", "1234", "十亿亿亿"] {
+            assert!(ast_number(src).is_err(), "{src}");
+        }
+    }
+
+    #[test]
+    fn test_same_as_number() {
+        let nums = (0..100_0000).step_by(7)
+            .chain([1_0000_0000, 1_0002_0000_0300, u64::MAX]);
+        for num in nums {
+            for s in [to_zh_num(num), to_zh_num_upper(num)] {
+                let (ast, _) = ast_number(&s).unwrap();
+                assert_eq!(ast.value(), Some(num), "{s}");
+                let sum = ast.terms().iter()
+                    .map(|t| t.digit * t.unit)
+                    .sum::<u64>();
+                assert_eq!(sum, num, "{s}");
+            }
+        }
+        for s in ["一万一", "两千零零一", "零零", "十亿零一", "一亿亿零三"] {
+            let (ast, rest) = ast_number(s).unwrap();
+            assert_eq!(Ok((ast.value().unwrap(), rest)), number(s), "{s}");
+        }
+    }
+}
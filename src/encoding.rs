@@ -0,0 +1,385 @@
+//! Adapters converting between UTF-8 and other encodings of [`encoding_rs`],
+//! used by the CLI with [`stream::convert_lines`]
+//!
+//! Requires the `encoding` feature
+//!
+//! [`stream::convert_lines`]: crate::stream::convert_lines
+
+use std::{
+    io::{self, BufRead, Read, Write},
+    mem, str,
+};
+
+use encoding_rs::{DecoderResult, Encoding, EncoderResult};
+
+/// A problem found by [`Decoder`] or [`Encoder`], the line number starts at 1
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum EncodingError {
+    /// Bytes which can't be decoded, replaced by `U+FFFD`,
+    /// or invalid UTF-8 passed to [`Encoder`], written as is.
+    /// The bytes may be incomplete when split across reads
+    Malformed { line: u64, bytes: Vec<u8> },
+    /// Char which can't be encoded, replaced by `?`
+    Unmappable { line: u64, ch: char },
+}
+
+/// Decode `input` to UTF-8, report the malformed bytes to `report`
+///
+/// # Examples
+/// ```
+/// # use std::io::Read;
+/// # use zh_num::encoding::{Decoder, EncodingError};
+/// let input: &[u8] = b"\xd2\xbb\xcd\xf2\n\xff\n";
+/// let mut errors = vec![];
+/// let mut s = String::new();
+/// Decoder::new(input, encoding_rs::GBK, |e| errors.push(e))
+///     .read_to_string(&mut s)
+///     .unwrap();
+/// assert_eq!(s, "一万\n\u{FFFD}\n");
+/// assert_eq!(errors, [EncodingError::Malformed { line: 2, bytes: vec![0xff] }]);
+/// ```
+pub struct Decoder<R, F> {
+    input: R,
+    decoder: encoding_rs::Decoder,
+    report: F,
+    buf: Vec<u8>,
+    pos: usize,
+    /// Lines of the decoded text before [`buf`](Self::buf)
+    lines: u64,
+    eof: bool,
+}
+impl<R, F> Decoder<R, F>
+where R: BufRead,
+      F: FnMut(EncodingError),
+{
+    pub fn new(input: R, encoding: &'static Encoding, report: F) -> Self {
+        Self {
+            input,
+            decoder: encoding.new_decoder_without_bom_handling(),
+            report,
+            buf: vec![],
+            pos: 0,
+            lines: 0,
+            eof: false,
+        }
+    }
+
+    /// Decode the next chunk of `input` into [`buf`](Self::buf)
+    fn decode_chunk(&mut self) -> io::Result<()> {
+        let src = self.input.fill_buf()?;
+        let last = src.is_empty();
+        let lines = self.lines;
+        let buf = &mut self.buf;
+        buf.clear();
+        buf.resize(self.decoder.max_utf8_buffer_length(src.len()).unwrap(), 0);
+        let (mut read, mut written) = (0, 0);
+        loop {
+            let (result, r, w) = self.decoder
+                .decode_to_utf8_without_replacement(&src[read..], &mut buf[written..], last);
+            read += r;
+            written += w;
+            match result {
+                DecoderResult::InputEmpty => break,
+                DecoderResult::OutputFull => buf.resize(buf.len() * 2 + 16, 0),
+                DecoderResult::Malformed(len, extra) => {
+                    let end = read - extra as usize;
+                    let bytes = src[end.saturating_sub(len as usize)..end].to_vec();
+                    let line = lines + 1 + count_lines(&buf[..written]);
+                    (self.report)(EncodingError::Malformed { line, bytes });
+                    let replacement = char::REPLACEMENT_CHARACTER.len_utf8();
+                    if buf.len() - written < replacement {
+                        buf.resize(written + replacement, 0);
+                    }
+                    written += char::REPLACEMENT_CHARACTER
+                        .encode_utf8(&mut buf[written..])
+                        .len();
+                },
+            }
+        }
+        let len = src.len();
+        self.input.consume(len);
+        self.buf.truncate(written);
+        self.pos = 0;
+        self.lines += count_lines(&self.buf);
+        self.eof = last;
+        Ok(())
+    }
+}
+impl<R, F> Read for Decoder<R, F>
+where R: BufRead,
+      F: FnMut(EncodingError),
+{
+    fn read(&mut self, out: &mut [u8]) -> io::Result<usize> {
+        let buf = self.fill_buf()?;
+        let len = buf.len().min(out.len());
+        out[..len].copy_from_slice(&buf[..len]);
+        self.consume(len);
+        Ok(len)
+    }
+}
+impl<R, F> BufRead for Decoder<R, F>
+where R: BufRead,
+      F: FnMut(EncodingError),
+{
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        while self.pos == self.buf.len() && !self.eof {
+            self.decode_chunk()?;
+        }
+        Ok(&self.buf[self.pos..])
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.pos += amt;
+    }
+}
+
+/// Encode the UTF-8 written to `output`, report the problems to `report`
+///
+/// A char may be split across writes.
+/// The encoder is finished when dropped and the errors are ignored,
+/// use [`finish`](Self::finish) to handle them
+///
+/// # Examples
+/// ```
+/// # use std::io::Write;
+/// # use zh_num::encoding::{Encoder, EncodingError};
+/// let mut output = vec![];
+/// let mut errors = vec![];
+/// let mut encoder = Encoder::new(&mut output, encoding_rs::GBK, |e| errors.push(e));
+/// encoder.write_all(&"一万\n😀".as_bytes()[..4]).unwrap();
+/// encoder.write_all(&"一万\n😀".as_bytes()[4..]).unwrap();
+/// encoder.finish().unwrap();
+/// drop(encoder);
+/// assert_eq!(output, b"\xd2\xbb\xcd\xf2\n?");
+/// assert_eq!(errors, [EncodingError::Unmappable { line: 2, ch: '😀' }]);
+/// ```
+pub struct Encoder<W: Write, F: FnMut(EncodingError)> {
+    output: W,
+    encoder: encoding_rs::Encoder,
+    report: F,
+    /// Incomplete UTF-8 at the end of the last write
+    pending: Vec<u8>,
+    buf: Vec<u8>,
+    /// Lines of the encoded text
+    lines: u64,
+    finished: bool,
+}
+impl<W, F> Encoder<W, F>
+where W: Write,
+      F: FnMut(EncodingError),
+{
+    pub fn new(output: W, encoding: &'static Encoding, report: F) -> Self {
+        Self {
+            output,
+            encoder: encoding.new_encoder(),
+            report,
+            pending: vec![],
+            buf: vec![0; 1024],
+            lines: 0,
+            finished: false,
+        }
+    }
+
+    fn encode(&mut self, mut src: &str, last: bool) -> io::Result<()> {
+        loop {
+            let (result, read, written) = self.encoder
+                .encode_from_utf8_without_replacement(src, &mut self.buf, last);
+            self.output.write_all(&self.buf[..written])?;
+            self.lines += count_lines(&src.as_bytes()[..read]);
+            src = &src[read..];
+            match result {
+                EncoderResult::InputEmpty => break Ok(()),
+                EncoderResult::OutputFull => (),
+                EncoderResult::Unmappable(ch) => {
+                    (self.report)(EncodingError::Unmappable { line: self.lines + 1, ch });
+                    self.output.write_all(b"?")?;
+                },
+            }
+        }
+    }
+
+    /// Write invalid UTF-8 as is
+    fn write_malformed(&mut self, bytes: &[u8]) -> io::Result<()> {
+        let line = self.lines + 1;
+        (self.report)(EncodingError::Malformed { line, bytes: bytes.to_vec() });
+        self.output.write_all(bytes)
+    }
+
+    /// Write the incomplete UTF-8 as is, finish the encoder and flush `output`
+    ///
+    /// Writing after finished is an error
+    pub fn finish(&mut self) -> io::Result<()> {
+        if self.finished {
+            return Ok(());
+        }
+        self.finished = true;
+        let pending = mem::take(&mut self.pending);
+        if !pending.is_empty() {
+            self.write_malformed(&pending)?;
+        }
+        self.encode("", true)?;
+        self.output.flush()
+    }
+}
+impl<W, F> Write for Encoder<W, F>
+where W: Write,
+      F: FnMut(EncodingError),
+{
+    fn write(&mut self, data: &[u8]) -> io::Result<usize> {
+        if self.finished {
+            return Err(io::Error::other("write after finished"));
+        }
+        let mut pending = mem::take(&mut self.pending);
+        pending.extend_from_slice(data);
+        let mut rest = &pending[..];
+        while !rest.is_empty() {
+            let (valid, invalid) = match str::from_utf8(rest) {
+                Ok(s) => (s, &[][..]),
+                Err(e) => {
+                    let (valid, invalid) = rest.split_at(e.valid_up_to());
+                    (str::from_utf8(valid).unwrap(), invalid)
+                },
+            };
+            self.encode(valid, false)?;
+            rest = invalid;
+            let Some(len) = str::from_utf8(rest).err().and_then(|e| e.error_len()) else {
+                // incomplete char, wait for the next write
+                break;
+            };
+            self.write_malformed(&rest[..len])?;
+            rest = &rest[len..];
+        }
+        self.pending = rest.to_vec();
+        Ok(data.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.output.flush()
+    }
+}
+impl<W, F> Drop for Encoder<W, F>
+where W: Write,
+      F: FnMut(EncodingError),
+{
+    fn drop(&mut self) {
+        let _ = self.finish();
+    }
+}
+
+fn count_lines(bytes: &[u8]) -> u64 {
+    bytes.iter().filter(|&&b| b == b'\n').count() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stream::{convert_lines, HEAD_LEN};
+    use encoding_rs::{BIG5, GB18030, GBK};
+
+    fn decode(input: &[u8], encoding: &'static Encoding, cap: usize) -> (String, Vec<EncodingError>) {
+        let mut errors = vec![];
+        let mut s = String::new();
+        let input = io::BufReader::with_capacity(cap, input);
+        Decoder::new(input, encoding, |e| errors.push(e))
+            .read_to_string(&mut s)
+            .unwrap();
+        (s, errors)
+    }
+
+    fn encode(chunks: &[&[u8]], encoding: &'static Encoding) -> (Vec<u8>, Vec<EncodingError>) {
+        let mut errors = vec![];
+        let mut output = vec![];
+        let mut encoder = Encoder::new(&mut output, encoding, |e| errors.push(e));
+        for chunk in chunks {
+            encoder.write_all(chunk).unwrap();
+        }
+        encoder.finish().unwrap();
+        drop(encoder);
+        (output, errors)
+    }
+
+    /// Line number and bytes of the malformed sequences
+    type Malformed<'a> = &'a [(u64, &'a [u8])];
+
+    #[test]
+    fn test_decode() {
+        let (gbk, _, _) = GBK.encode("一万\n三\n");
+        let (big5, _, _) = BIG5.encode("一萬\n三\n");
+        let datas: [(&[u8], &'static Encoding, &str, Malformed<'_>); 6] = [
+            (&gbk, GBK, "一万\n三\n", &[]),
+            (&big5, BIG5, "一萬\n三\n", &[]),
+            (b"\xd2\xbb\n\xff\n\n\xff", GBK, "一\n\u{FFFD}\n\n\u{FFFD}", &[(2, b"\xff"), (4, b"\xff")]),
+            (b"a\nb\n\xd2", GBK, "a\nb\n\u{FFFD}", &[(3, b"")]),
+            (b"\xa4@\n\x80\n", BIG5, "一\n\u{FFFD}\n", &[(2, b"\x80")]),
+            (b"\n\n\x80a\n", BIG5, "\n\n\u{FFFD}a\n", &[(3, b"\x80")]),
+        ];
+        for (input, encoding, expected, errors) in datas {
+            for cap in [1, 2, 3, 8192] {
+                let (s, errs) = decode(input, encoding, cap);
+                assert_eq!(s, expected, "{input:02X?} {cap}");
+                let lines = errs.iter()
+                    .map(|e| match e {
+                        EncodingError::Malformed { line, .. } => *line,
+                        e => panic!("{e:?}"),
+                    })
+                    .collect::<Vec<_>>();
+                let expected_lines = errors.iter().map(|e| e.0).collect::<Vec<_>>();
+                assert_eq!(lines, expected_lines, "{input:02X?} {cap}");
+                if cap == 8192 {
+                    for (err, (_, bytes)) in errs.iter().zip(errors) {
+                        let EncodingError::Malformed { bytes: b, .. } = err else { unreachable!() };
+                        assert_eq!(b, bytes, "{input:02X?}");
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_encode() {
+        let s = "一万\n😀三\n";
+        let (gbk, _, _) = GBK.encode("一万\n?三\n");
+        let bytes = s.as_bytes();
+        // every split point, includes inside a char
+        for i in 0..=bytes.len() {
+            let (output, errors) = encode(&[&bytes[..i], &bytes[i..]], GBK);
+            assert_eq!(output, &*gbk, "{i}");
+            assert_eq!(errors, [EncodingError::Unmappable { line: 2, ch: '😀' }], "{i}");
+        }
+        let chunks = bytes.iter().map(std::slice::from_ref).collect::<Vec<_>>();
+        assert_eq!(encode(&chunks, GBK).0, &*gbk);
+
+        let (output, errors) = encode(&[b"a\n\xff\nb", b"\xe4\xb8"], GBK);
+        assert_eq!(output, b"a\n\xff\nb\xe4\xb8");
+        assert_eq!(errors, [
+            EncodingError::Malformed { line: 2, bytes: vec![0xff] },
+            EncodingError::Malformed { line: 3, bytes: vec![0xe4, 0xb8] },
+        ]);
+    }
+
+    #[test]
+    fn test_long_line() {
+        let long = "章".repeat(HEAD_LEN);
+        let text = format!("十二{long}\n三😀\n");
+        let (input, _, _) = GB18030.encode(&text);
+        let mut output = vec![];
+        let (mut decode_errors, mut errors) = (vec![], vec![]);
+        let decoder = Decoder::new(
+            io::BufReader::with_capacity(1000, &*input),
+            GB18030,
+            |e| decode_errors.push(e),
+        );
+        let mut encoder = Encoder::new(&mut output, GBK, |e| errors.push(e));
+        convert_lines(decoder, &mut encoder, true, |_, head, out| {
+            out.write_all(head.as_bytes())?;
+            Ok(head.len())
+        }).unwrap();
+        encoder.finish().unwrap();
+        drop(encoder);
+        let expected = format!("十二{long}\n三?\n");
+        let (expected, _, _) = GBK.encode(&expected);
+        assert_eq!(output, &*expected);
+        assert_eq!(decode_errors, []);
+        assert_eq!(errors, [EncodingError::Unmappable { line: 2, ch: '😀' }]);
+    }
+}
@@ -0,0 +1,599 @@
+//! Hand-written single pass parsers and formatters,
+//! without backtracking, allocation and [`fmt`](std::fmt) machinery
+//!
+//! The parser semantics are the same as [`parser`],
+//! but return [`None`] instead of a detailed error,
+//! [`try_number`] and [`try_hard_number`] run the peg grammar only on failure
+//! to get the detailed error
+//!
+//! The peg grammar stays as the reference implementation,
+//! it reports the error location and expected tokens,
+//! and parses the other notations built on [`parser::number`],
+//! e.g ranges, approximate quantities and power notation.
+//! The parsers here are tested against it exhaustively for short inputs
+//!
+//! [`parser::number`]: crate::parser::number
+//!
+//! The formatter output is the same as [`to_zh_num`] and [`to_zh_num_upper`]
+//!
+//! [`to_zh_num`]: crate::to_zh_num
+//! [`to_zh_num_upper`]: crate::to_zh_num_upper
+
+use crate::{parser, LowerNum, NumCfg, Number, ParseError, UpperNum};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Tok {
+    Zero,
+    Digit(Number),
+    N10,
+    N100,
+    N1000,
+    N1_0000,
+    N1_0000_0000,
+    /// ASCII or full-width digit
+    Ascii(Number),
+    Other,
+}
+
+impl Tok {
+    /// Power of the unit, or zero
+    const fn pow(self) -> Number {
+        match self {
+            Tok::N10 => 10,
+            Tok::N100 => 100,
+            Tok::N1000 => 1000,
+            Tok::N1_0000 => 1_0000,
+            Tok::N1_0000_0000 => 1_0000_0000,
+            _ => 0,
+        }
+    }
+}
+
+/// Is the char at `i` equal to `ch`
+const fn is_char(s: &[u8], i: usize, ch: &str) -> bool {
+    let ch = ch.as_bytes();
+    let mut j = 0;
+    while j < ch.len() {
+        if i + j >= s.len() || s[i + j] != ch[j] {
+            return false;
+        }
+        j += 1;
+    }
+    true
+}
+
+/// `?` of [`Option`] in const fn
+macro_rules! tri {
+    ($e:expr) => {
+        match $e {
+            Some(x) => x,
+            None => return None,
+        }
+    };
+}
+
+/// Decode the char at `i`, return the token and the next index
+const fn tok(s: &[u8], i: usize) -> (Tok, usize) {
+    if i >= s.len() {
+        return (Tok::Other, i);
+    }
+    let b0 = s[i];
+    if b0.is_ascii_digit() {
+        return (Tok::Ascii((b0 - b'0') as Number), i + 1);
+    }
+    // all the chars we care about are 3-byte sequences
+    if b0 & 0xF0 != 0xE0 || i + 2 >= s.len() {
+        return (Tok::Other, i);
+    }
+    let ch = ((b0 & 0x0F) as u32) << 12
+        | ((s[i + 1] & 0x3F) as u32) << 6
+        | (s[i + 2] & 0x3F) as u32;
+    let Some(ch) = char::from_u32(ch) else { return (Tok::Other, i) };
+    let tok = match ch {
+        '零' | '〇' => Tok::Zero,
+        '一' | '壹' | '弌' | '幺' => Tok::Digit(1),
+        '二' | '贰' | '弍' | '两' => Tok::Digit(2),
+        '三' | '叁' | '弎' => Tok::Digit(3),
+        '四' | '肆' => Tok::Digit(4),
+        '五' | '伍' => Tok::Digit(5),
+        '六' | '陆' => Tok::Digit(6),
+        '七' | '柒' => Tok::Digit(7),
+        '八' | '捌' => Tok::Digit(8),
+        '九' | '玖' => Tok::Digit(9),
+        '十' | '拾' => Tok::N10,
+        '百' | '佰' | '陌' => Tok::N100,
+        '千' | '仟' | '阡' => Tok::N1000,
+        '万' => Tok::N1_0000,
+        '亿' => Tok::N1_0000_0000,
+        '０'..='９' => Tok::Ascii((ch as u32 - '０' as u32) as Number),
+        _ => return (Tok::Other, i),
+    };
+    (tok, i + 3)
+}
+
+/// Zeros and then a digit, the digit is [`None`] when only zeros
+const fn one_num(s: &[u8], i: usize) -> Option<(Option<Number>, usize)> {
+    let mut j = i;
+    loop {
+        match tok(s, j) {
+            (Tok::Zero, next) => j = next,
+            (Tok::Digit(n), next) => break Some((Some(n), next)),
+            _ if j != i => break Some((None, j)),
+            _ => break None,
+        }
+    }
+}
+
+const fn k_number(s: &[u8], mut i: usize) -> Option<(Number, usize)> {
+    let mut sum = None;
+    let mut one = one_num(s, i);
+    let mut pow = 1000;
+    while pow > 10 {
+        if let Some((n, j)) = one {
+            let (tok, next) = tok(s, j);
+            if tok.pow() == pow {
+                let n = match n { Some(n) => n, None => 0 };
+                sum = Some(match sum { Some(sum) => sum, None => 0 } + n * pow);
+                i = next;
+                one = one_num(s, i);
+            }
+        }
+        pow /= 10;
+    }
+    let (n, j) = match one {
+        Some((n, j)) => (n, j),
+        None => (Some(1), i),
+    };
+    if let (Tok::N10, next) = tok(s, j) {
+        let n = match n { Some(n) => n, None => 1 };
+        sum = Some(match sum { Some(sum) => sum, None => 0 } + n * 10);
+        i = next;
+        one = one_num(s, i);
+    }
+    if let Some((n, j)) = one {
+        let n = match n { Some(n) => n, None => 0 };
+        sum = Some(match sum { Some(sum) => sum, None => 0 } + n);
+        i = j;
+    }
+    match sum {
+        Some(sum) => Some((sum, i)),
+        None => None,
+    }
+}
+
+const fn wan_number(s: &[u8], i: usize) -> Option<(Number, usize)> {
+    let (w, i) = tri!(k_number(s, i));
+    let (Tok::N1_0000, next) = tok(s, i) else { return Some((w, i)) };
+    let (n, i) = match k_number(s, next) {
+        Some(x) => x,
+        None => (0, next),
+    };
+    Some((tri!(tri!(w.checked_mul(1_0000)).checked_add(n)), i))
+}
+
+const fn yi_number(s: &[u8], i: usize) -> Option<(Number, usize)> {
+    let (mut high, mut i) = tri!(wan_number(s, i));
+    while let (Tok::N1_0000_0000, next) = tok(s, i) {
+        let (n, j) = match wan_number(s, next) {
+            Some(x) => x,
+            None => (0, next),
+        };
+        high = tri!(tri!(high.checked_mul(1_0000_0000)).checked_add(n));
+        i = j;
+    }
+    Some((high, i))
+}
+
+const fn ascii_number(s: &[u8], mut i: usize) -> Option<(Number, usize)> {
+    let mut num = None;
+    while let (Tok::Ascii(d), next) = tok(s, i) {
+        let acc = match num { Some(num) => num, None => 0 };
+        num = Some(tri!(tri!(Number::checked_mul(acc, 10)).checked_add(d)));
+        i = next;
+    }
+    match num {
+        Some(num) => Some((num, i)),
+        None => None,
+    }
+}
+
+/// Parse zh nums, return parsed number and rest text,
+/// like [`parser::number`]
+///
+/// # Examples
+/// ```
+/// # use zh_num::fast::number;
+/// assert_eq!(number("一万零十三章"), Some((10013, "章")));
+/// assert_eq!(number("１２３４章"), Some((1234, "章")));
+/// assert_eq!(number("章"), None);
+/// ```
+///
+/// [`parser::number`]: crate::parser::number
+pub const fn number(s: &str) -> Option<(Number, &str)> {
+    let bytes = s.as_bytes();
+    let (num, i) = match tok(bytes, 0).0 {
+        Tok::Ascii(_) => tri!(ascii_number(bytes, 0)),
+        _ => tri!(yi_number(bytes, 0)),
+    };
+    Some((num, s.split_at(i).1))
+}
+
+/// Parse hard zh nums, return parsed number and rest text,
+/// like [`parser::hard_number`]
+///
+/// # Examples
+/// ```
+/// # use zh_num::fast::hard_number;
+/// assert_eq!(hard_number("一零零八六章"), Some((10086, "章")));
+/// assert_eq!(hard_number("百零零章"), Some((100, "章")));
+/// ```
+///
+/// [`parser::hard_number`]: crate::parser::hard_number
+pub const fn hard_number(s: &str) -> Option<(Number, &str)> {
+    let bytes = s.as_bytes();
+    let mut i = 0;
+    let mut num = None;
+    loop {
+        let (d, next) = match tok(bytes, i) {
+            (Tok::Zero, next) => (0, next),
+            (Tok::Digit(n), next) => (n, next),
+            // hard units are only the lower chars
+            (Tok::N10, next) if is_char(bytes, i, "十") => (1, next),
+            (Tok::N100, next) if is_char(bytes, i, "百") => (1, next),
+            (Tok::N1000, next) if is_char(bytes, i, "千") => (1, next),
+            (Tok::N1_0000 | Tok::N1_0000_0000, next) => (1, next),
+            _ => break,
+        };
+        let acc = match num { Some(num) => num, None => 0 };
+        num = Some(tri!(tri!(Number::checked_mul(acc, 10)).checked_add(d)));
+        i = next;
+    }
+    match num {
+        Some(num) => Some((num, s.split_at(i).1)),
+        None => None,
+    }
+}
+
+/// Like [`number`], but returns the error of [`parser::number`],
+/// which only runs when [`number`] failed
+///
+/// # Examples
+/// ```
+/// # use zh_num::fast::try_number;
+/// assert_eq!(try_number("一万零十三章"), Ok((10013, "章")));
+/// assert_eq!(try_number("章").unwrap_err().location.offset, 0);
+/// ```
+pub fn try_number(s: &str) -> Result<(Number, &str), ParseError> {
+    number(s).map_or_else(|| parser::number(s), Ok)
+}
+
+/// Like [`hard_number`], but returns the error of [`parser::hard_number`],
+/// which only runs when [`hard_number`] failed
+///
+/// # Examples
+/// ```
+/// # use zh_num::fast::try_hard_number;
+/// assert_eq!(try_hard_number("一零零八六章"), Ok((10086, "章")));
+/// assert!(try_hard_number("章").is_err());
+/// ```
+pub fn try_hard_number(s: &str) -> Result<(Number, &str), ParseError> {
+    hard_number(s).map_or_else(|| parser::hard_number(s), Ok)
+}
+
+/// Max bytes of the zh numbers of any [`Number`]
+///
+/// At most 5 sections of 7 chars, 4 chars of `万` `亿`,
+/// each char is 3 bytes
+pub const MAX_LEN: usize = (5 * 7 + 4) * 3;
+
+struct Table {
+    digits: [[u8; 3]; 10],
+    /// Units of `10^1` `10^2` `10^3`, the first is unused
+    units: [[u8; 3]; 4],
+    n1_0000: [u8; 3],
+    n1_0000_0000: [u8; 3],
+    leading_one: bool,
+}
+impl Table {
+    const fn new<C: NumCfg>(leading_one: bool) -> Self {
+        let mut digits = [[0; 3]; 10];
+        let mut i = 0;
+        while i < digits.len() {
+            digits[i] = utf8(C::DIGITS[i]);
+            i += 1;
+        }
+        Self {
+            digits,
+            units: [[0; 3], utf8(C::N10), utf8(C::N100), utf8(C::N1000)],
+            n1_0000: utf8(C::N1_0000),
+            n1_0000_0000: utf8(C::N1_0000_0000),
+            leading_one,
+        }
+    }
+}
+
+const LOWER: Table = Table::new::<LowerNum>(false);
+const UPPER: Table = Table::new::<UpperNum>(true);
+
+/// Encode a char of `U+0800..=U+FFFF`
+const fn utf8(ch: char) -> [u8; 3] {
+    let ch = ch as u32;
+    assert!(ch >= 0x800 && ch <= 0xFFFF);
+    [
+        0xE0 | (ch >> 12) as u8,
+        0x80 | (ch >> 6 & 0x3F) as u8,
+        0x80 | (ch & 0x3F) as u8,
+    ]
+}
+
+struct Writer<'a> {
+    buf: &'a mut [u8; MAX_LEN],
+    len: usize,
+    /// Same as the `sp` of [`NumCfg::unit`]
+    sp: Option<bool>,
+}
+impl Writer<'_> {
+    const fn push(&mut self, ch: [u8; 3]) {
+        self.buf[self.len] = ch[0];
+        self.buf[self.len + 1] = ch[1];
+        self.buf[self.len + 2] = ch[2];
+        self.len += 3;
+    }
+
+    const fn unit(&mut self, t: &Table, num: Number) {
+        let mut pow_d = 4;
+        while pow_d > 0 {
+            pow_d -= 1;
+            let digit = num / Number::pow(10, pow_d) % 10;
+            if digit == 0 {
+                if let Some(x) = &mut self.sp { *x = true }
+                continue;
+            }
+            if let Some(true) = self.sp { self.push(t.digits[0]) }
+            if !(self.sp.is_none() && digit == 1 && pow_d == 1)
+                || t.leading_one
+            {
+                self.push(t.digits[digit as usize]);
+            }
+            if pow_d != 0 {
+                self.push(t.units[pow_d as usize]);
+            }
+            self.sp = Some(false);
+        }
+    }
+
+    const fn part(&mut self, t: &Table, num: Number) {
+        match num {
+            0..=9999 => self.unit(t, num),
+            1_0000..=9999_9999 => {
+                self.part(t, num / 1_0000);
+                self.push(t.n1_0000);
+                self.part(t, num % 1_0000);
+            },
+            1_0000_0000..=Number::MAX => {
+                self.part(t, num / 1_0000_0000);
+                self.push(t.n1_0000_0000);
+                self.part(t, num % 1_0000_0000);
+            },
+        }
+    }
+}
+
+const fn write<'a>(
+    t: &Table,
+    num: Number,
+    buf: &'a mut [u8; MAX_LEN],
+) -> &'a str {
+    let mut w = Writer { buf, len: 0, sp: None };
+    if num == 0 {
+        w.push(t.digits[0]);
+    } else {
+        w.part(t, num);
+    }
+    let len = w.len;
+    match std::str::from_utf8(buf.split_at(len).0) {
+        Ok(s) => s,
+        Err(_) => panic!("utf8 encoded chars"),
+    }
+}
+
+/// Write zh numbers into `buf`, return the written text,
+/// same as [`to_zh_num`]
+///
+/// # Examples
+/// ```
+/// # use zh_num::fast::{write_zh_num, MAX_LEN};
+/// let mut buf = [0; MAX_LEN];
+/// assert_eq!(write_zh_num(10086, &mut buf), "一万零八十六");
+/// ```
+///
+/// [`to_zh_num`]: crate::to_zh_num
+pub const fn write_zh_num(num: Number, buf: &mut [u8; MAX_LEN]) -> &str {
+    write(&LOWER, num, buf)
+}
+
+/// Write upper zh numbers into `buf`, return the written text,
+/// same as [`to_zh_num_upper`]
+///
+/// # Examples
+/// ```
+/// # use zh_num::fast::{write_zh_num_upper, MAX_LEN};
+/// let mut buf = [0; MAX_LEN];
+/// assert_eq!(write_zh_num_upper(10086, &mut buf), "壹万零捌拾陆");
+/// ```
+///
+/// [`to_zh_num_upper`]: crate::to_zh_num_upper
+pub const fn write_zh_num_upper(num: Number, buf: &mut [u8; MAX_LEN]) -> &str {
+    write(&UPPER, num, buf)
+}
+
+/// Append zh numbers to `s`, like [`write_zh_num`]
+///
+/// # Examples
+/// ```
+/// # use zh_num::fast::push_zh_num;
+/// let mut s = String::from("第");
+/// push_zh_num(10086, &mut s);
+/// assert_eq!(s, "第一万零八十六");
+/// ```
+pub fn push_zh_num(num: Number, s: &mut String) {
+    s.push_str(write_zh_num(num, &mut [0; MAX_LEN]));
+}
+
+/// Append upper zh numbers to `s`, like [`write_zh_num_upper`]
+///
+/// # Examples
+/// ```
+/// # use zh_num::fast::push_zh_num_upper;
+/// let mut s = String::from("人民币");
+/// push_zh_num_upper(10086, &mut s);
+/// assert_eq!(s, "人民币壹万零捌拾陆");
+/// ```
+pub fn push_zh_num_upper(num: Number, s: &mut String) {
+    s.push_str(write_zh_num_upper(num, &mut [0; MAX_LEN]));
+}
+
+/// Zh numbers in a fixed buffer, can be built in const context
+///
+/// # Examples
+/// ```
+/// # use zh_num::fast::ZhBuf;
+/// const TITLE: ZhBuf = ZhBuf::new(10086);
+/// assert_eq!(TITLE.as_str(), "一万零八十六");
+/// assert_eq!(ZhBuf::new_upper(10086).as_str(), "壹万零捌拾陆");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ZhBuf {
+    buf: [u8; MAX_LEN],
+    len: usize,
+}
+impl ZhBuf {
+    /// Build from [`write_zh_num`]
+    pub const fn new(num: Number) -> Self {
+        let mut buf = [0; MAX_LEN];
+        let len = write_zh_num(num, &mut buf).len();
+        Self { buf, len }
+    }
+
+    /// Build from [`write_zh_num_upper`]
+    pub const fn new_upper(num: Number) -> Self {
+        let mut buf = [0; MAX_LEN];
+        let len = write_zh_num_upper(num, &mut buf).len();
+        Self { buf, len }
+    }
+
+    pub const fn as_str(&self) -> &str {
+        match std::str::from_utf8(self.buf.split_at(self.len).0) {
+            Ok(s) => s,
+            Err(_) => panic!("utf8 encoded chars"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{to_zh_num, to_zh_num_upper};
+
+    type ParseFn = for<'a> fn(&'a str) -> Result<(Number, &'a str), ParseError>;
+
+    /// Result of the parser
+    fn expected(f: ParseFn, s: &str) -> Option<(Number, &str)> {
+        f(s).ok()
+    }
+
+    #[test]
+    fn test_same_as_parser() {
+        let srcs = [
+            "", "章", "零", "〇", "零零", "零否", "一", "十", "十零一", "一十零一",
+            "两千一", "零万", "万", "亿", "十万", "一万一", "一亿一", "一亿亿零一",
+            "一亿三亿零一", "一亿零三亿零一", "一千零四亿零一", "一万零一十三",
+            "陆仟零柒", "伍万零壹拾贰", "千", "百零零", "零千", "零百一十",
+            "一千一", "三百六十八万", "十十", "一百十", "零十", "一十十",
+            "1234", "１２３４章", "１2３4", "0", "18446744073709551615",
+            "18446744073709551616", "一亿亿亿", "十万亿亿", "一零零八六章",
+            "一零零十三章", "零零零章", "拾", "阡陌", "幺两", "〇一〇",
+            "一一", "一十一一", "一万万", "一万亿万", "二十一万零二百一十",
+            "1,234", "1,234,567章", "1234,5678", "1,2345,6789", "1,23", "1,",
+            "12345,678", "1,234,5678", "１,２３４", "18,446,744,073,709,551,616",
+        ];
+        for src in srcs {
+            assert_eq!(number(src), expected(parser::number, src), "{src}");
+            assert_eq!(hard_number(src), expected(parser::hard_number, src), "{src}");
+        }
+    }
+
+    #[test]
+    fn test_same_as_parser_range() {
+        (0..150)
+            .chain((150..10000000).step_by(293))
+            .chain((10000000..1000000000).step_by(38004))
+            .chain(1000000000..=1000000200)
+            .chain([Number::MAX])
+            .for_each(|n| {
+                for s in [to_zh_num(n), to_zh_num_upper(n), n.to_string()] {
+                    assert_eq!(number(&s), Some((n, "")), "{s}");
+                    let hard = expected(parser::hard_number, &s);
+                    assert_eq!(hard_number(&s), hard, "{s}");
+                }
+            });
+    }
+
+    #[test]
+    fn test_same_as_fmt() {
+        let mut buf = [0; MAX_LEN];
+        (0..150)
+            .chain((150..10000000).step_by(293))
+            .chain((10000000..1000000000).step_by(38004))
+            .chain((0..Number::MAX).step_by(1 << 48))
+            .chain([Number::MAX])
+            .for_each(|n| {
+                assert_eq!(write_zh_num(n, &mut buf), to_zh_num(n));
+                assert_eq!(write_zh_num_upper(n, &mut buf), to_zh_num_upper(n));
+            });
+    }
+
+    #[test]
+    fn test_const() {
+        const NUM: Option<(Number, &str)> = number("一万零八十六章");
+        const HARD: Option<(Number, &str)> = hard_number("一零零八六章");
+        const BUF: ZhBuf = ZhBuf::new(10086);
+        const UPPER_BUF: ZhBuf = ZhBuf::new_upper(10086);
+        assert_eq!(NUM, Some((10086, "章")));
+        assert_eq!(HARD, Some((10086, "章")));
+        assert_eq!(BUF.as_str(), "一万零八十六");
+        assert_eq!(UPPER_BUF.as_str(), "壹万零捌拾陆");
+        assert_eq!(crate::zh_num!("一万零八十六"), 10086u64);
+        assert_eq!(crate::zh_str!(10086), "一万零八十六");
+        assert_eq!(crate::zh_str_upper!(10086), "壹万零捌拾陆");
+    }
+
+    #[test]
+    fn test_max_len() {
+        // every digit is non-zero, zeros are shorter than the digit and unit
+        let mut buf = [0; MAX_LEN];
+        let num = 1111_1111_1111_1111_1111;
+        assert_eq!(write_zh_num(num, &mut buf).len(), MAX_LEN);
+        assert_eq!(write_zh_num_upper(num, &mut buf).len(), MAX_LEN);
+    }
+
+    #[test]
+    fn test_same_as_parser_exhaustive() {
+        const CHARS: [&str; 10] = [
+            "零", "一", "二", "十", "百", "千", "万", "亿", "拾", "章",
+        ];
+        let mut s = String::new();
+        for len in 1..=5u32 {
+            for mut i in 0..CHARS.len().pow(len) {
+                s.clear();
+                for _ in 0..len {
+                    s.push_str(CHARS[i % CHARS.len()]);
+                    i /= CHARS.len();
+                }
+                assert_eq!(number(&s), expected(parser::number, &s), "{s}");
+                let hard = expected(parser::hard_number, &s);
+                assert_eq!(hard_number(&s), hard, "{s}");
+            }
+        }
+    }
+}
@@ -0,0 +1,60 @@
+/// Primitive integers which can be converted from and to zh numbers,
+/// see [`parser::integer`] and [`to_zh_int`]
+///
+/// [`parser::integer`]: crate::parser::integer
+/// [`to_zh_int`]: crate::to_zh_int
+pub trait ZhInt: Copy {
+    /// Split into the sign and absolute value, `true` is negative
+    fn to_parts(self) -> (bool, u128);
+
+    /// Build from the sign and absolute value, [`None`] if out of range
+    ///
+    /// # Examples
+    /// ```
+    /// # use zh_num::ZhInt;
+    /// assert_eq!(i8::from_parts(true, 128), Some(-128));
+    /// assert_eq!(i8::from_parts(false, 128), None);
+    /// assert_eq!(u8::from_parts(true, 1), None);
+    /// assert_eq!(u8::from_parts(true, 0), Some(0));
+    /// ```
+    fn from_parts(negative: bool, abs: u128) -> Option<Self>;
+}
+
+macro_rules! impl_unsigned {
+    ($($ty:ty),*) => {$(
+        impl ZhInt for $ty {
+            fn to_parts(self) -> (bool, u128) {
+                (false, self as u128)
+            }
+
+            fn from_parts(negative: bool, abs: u128) -> Option<Self> {
+                if negative && abs != 0 {
+                    return None;
+                }
+                Self::try_from(abs).ok()
+            }
+        }
+    )*};
+}
+
+macro_rules! impl_signed {
+    ($($ty:ty),*) => {$(
+        impl ZhInt for $ty {
+            fn to_parts(self) -> (bool, u128) {
+                (self < 0, self.unsigned_abs() as u128)
+            }
+
+            fn from_parts(negative: bool, abs: u128) -> Option<Self> {
+                if negative {
+                    let num = 0i128.checked_sub_unsigned(abs)?;
+                    Self::try_from(num).ok()
+                } else {
+                    Self::try_from(abs).ok()
+                }
+            }
+        }
+    )*};
+}
+
+impl_unsigned!(u8, u16, u32, u64, u128, usize);
+impl_signed!(i8, i16, i32, i64, i128, isize);
@@ -1,10 +1,101 @@
 use std::{
     cell::Cell,
     fmt::{self, Display},
+    marker::PhantomData,
+    num::ParseIntError,
+    ops::RangeInclusive,
+    str::FromStr,
 };
 
+pub mod ast;
+#[cfg(feature = "encoding")]
+pub mod encoding;
+pub mod fast;
+pub mod stream;
+mod approx;
+mod int;
+mod lint;
+mod parsed;
+mod readings;
+mod upper;
+
+pub use approx::{Approx, Qualifier};
+pub use int::ZhInt;
+pub use lint::{fix_lints, lint, Lint, Rule};
+pub use parsed::{parse_number, parse_number_with, ParseOptions, Parsed, Style};
+pub use readings::{readings, Reading, ReadingKind};
+pub use upper::{upper_number, UpperError, UpperErrorKind};
+
+/// Parse zh numbers at compile time, see [`fast::number`]
+///
+/// # Examples
+/// ```
+/// # use zh_num::zh_num;
+/// const N: u64 = zh_num!("一万零八十六");
+/// assert_eq!(N, 10086);
+/// assert_eq!(zh_num!("１２３４"), 1234);
+/// ```
+///
+/// Invalid input fails the compilation
+///
+/// ```compile_fail
+/// # use zh_num::zh_num;
+/// let n = zh_num!("一万零八十六章");
+/// ```
+#[macro_export]
+macro_rules! zh_num {
+    ($s:literal) => {
+        const {
+            match $crate::fast::number($s) {
+                ::core::option::Option::Some((n, rest)) if rest.is_empty() => n,
+                _ => ::core::panic!(::core::concat!("invalid zh number: ", $s)),
+            }
+        }
+    };
+}
+
+/// Format zh numbers at compile time, see [`fast::ZhBuf`]
+///
+/// # Examples
+/// ```
+/// # use zh_num::zh_str;
+/// const S: &str = zh_str!(10086);
+/// assert_eq!(S, "一万零八十六");
+/// ```
+#[macro_export]
+macro_rules! zh_str {
+    ($n:expr) => {{
+        const S: &::core::primitive::str = {
+            const BUF: $crate::fast::ZhBuf = $crate::fast::ZhBuf::new($n);
+            BUF.as_str()
+        };
+        S
+    }};
+}
+
+/// Format upper zh numbers at compile time, see [`fast::ZhBuf`]
+///
+/// # Examples
+/// ```
+/// # use zh_num::zh_str_upper;
+/// assert_eq!(zh_str_upper!(10086), "壹万零捌拾陆");
+/// ```
+#[macro_export]
+macro_rules! zh_str_upper {
+    ($n:expr) => {{
+        const S: &::core::primitive::str = {
+            const BUF: $crate::fast::ZhBuf = $crate::fast::ZhBuf::new_upper($n);
+            BUF.as_str()
+        };
+        S
+    }};
+}
+
 pub type Number = u64;
 
+/// Error of [`parser`]
+pub type ParseError = peg::error::ParseError<peg::str::LineCol>;
+
 peg::parser!(pub grammar parser() for str {
     pub rule one_num(d: Number) -> Number
         = ['零' | '〇'] n:one_num(d)?   { n.unwrap_or(d) }
@@ -20,45 +111,393 @@ peg::parser!(pub grammar parser() for str {
     rule power_num() -> Number
         = "亿" { 100000000 }
         / "万" { 10000 }
-    rule k_number() -> Number
-        = a:(n:one_num(0)  ['千' | '仟' | '阡'] { 1000 * n })?
-          b:(n:one_num(0)  ['百' | '佰' | '陌'] { 100 * n })?
-          c:(n:one_num(1)? ['十' | '拾']        { 10 * n.unwrap_or(1) })?
-          d:(n:one_num(0)                       { n })?
+    rule k_number() -> ast::KNumber
+        = a:(n:one_num(0)  ['千' | '仟' | '阡'] { (n, 1000) })?
+          b:(n:one_num(0)  ['百' | '佰' | '陌'] { (n, 100) })?
+          c:(n:one_num(1)? ['十' | '拾']        { (n.unwrap_or(1), 10) })?
+          d:(n:one_num(0)                       { (n, 1) })?
         {?
-            [a, b, c, d].into_iter()
+            let terms = [a, b, c, d].into_iter()
                 .flatten()
-                .reduce(|a, b| a + b)
-                .ok_or("num-unit")
+                .map(|(digit, unit)| ast::Term { digit, unit })
+                .collect::<Vec<_>>();
+            if terms.is_empty() {
+                Err("num-unit")
+            } else {
+                Ok(ast::KNumber { terms })
+            }
         }
-    rule wan_number() -> Number
-        = w:k_number() n:("万" n:k_number()? { n.unwrap_or_default() })?
+    rule wan_number() -> ast::WanNumber
+        = w:k_number() n:("万" n:k_number()? { n })?
         {
-            n.map(|n| w * 10000 + n)
-                .unwrap_or(w)
+            match n {
+                Some(n) => ast::WanNumber { wan: Some(w), rest: n },
+                None => ast::WanNumber { wan: None, rest: Some(w) },
+            }
         }
-    rule yi_number() -> Number
+    rule yi_sections() -> ast::YiNumber
         = w:wan_number() rest:("亿" x:wan_number()? { x.unwrap_or_default() })*
-        {
-            rest.into_iter().fold(w, |high, n| {
-                high * 1_0000_0000 + n
-            })
+        { ast::YiNumber { sections: [w].into_iter().chain(rest).collect() } }
+    rule yi_wide() -> u128
+        = a:yi_sections() {? a.wide_value().ok_or("valid-number") }
+    rule yi_number() -> Number
+        = a:yi_sections() {? a.value().ok_or("valid-number") }
+    rule yi_ast() -> ast::YiNumber
+        = a:yi_sections() {? a.value().map(|_| a).ok_or("valid-number") }
+    rule unit_pow() -> u32
+        = ps:(
+            ['十' | '拾'] { 1 }
+            / ['百' | '佰'] { 2 }
+            / ['千' | '仟'] { 3 }
+            / "万" { 4 }
+            / "亿" { 8 })+
+        { ps.into_iter().sum() }
+    rule suzhou_digit() -> Number
+        = "〇" { 0 }
+        / ch:['〡'..='〩'] { ch as Number - '〠' as Number }
+        / "一" { 1 }
+        / "二" { 2 }
+        / "三" { 3 }
+    rule rod_digit() -> Number
+        = ch:['\u{1D360}'..='\u{1D368}'] { ch as Number - 0x1D35F }
+        / ch:['\u{1D369}'..='\u{1D371}'] { ch as Number - 0x1D368 }
+    rule rod_zero() -> Number
+        = "〇" { 0 }
+        / " " &(" "* rod_digit()) { 0 }
+    rule ascii_digit() = ['0'..='9' | '０'..='９']
+    rule ascii_number() -> Number
+        = s:$(ascii_digit()+) {? parse_digits(s).map_err(|_| "valid-number") }
+    rule ascii_wide() -> u128
+        = s:$(ascii_digit()+) {? parse_digits_as(s).map_err(|_| "valid-number") }
+    rule grouped_number() -> Number
+        = s:$(
+            ascii_digit()*<1,3> ("," ascii_digit()*<3> !ascii_digit())+
+            / ascii_digit()*<1,4> ("," ascii_digit()*<4> !ascii_digit())+
+            / ascii_digit()+)
+        {?
+            parse_digits(&s.replace(',', "")).map_err(|_| "valid-number")
         }
     rule raw_number() -> Number
-        = (s:$(['0'..='9']+) {? s.parse().map_err(|_| "valid-number") })
+        = ascii_number()
         / yi_number()
 
     /// Parse zh nums, return parsed number and rest text
     ///
+    /// ASCII digits are not grouped, see [`digits`] for grouped digits
+    ///
     /// # Examples
     /// ```
     /// # use zh_num::parser::number;
     /// assert_eq!(number("一万零十三章"), Ok((10013, "章")));
+    /// assert_eq!(number("2023,2024年"), Ok((2023, ",2024年")));
     /// ```
     pub rule number() -> (Number, &'input str)
         = n:raw_number() s:$([_]*)
         { (n, s) }
 
+    /// Parse ASCII or full-width digits, return parsed number and rest text
+    ///
+    /// Digits may be grouped by `,` every three or four digits
+    ///
+    /// # Examples
+    /// ```
+    /// # use zh_num::parser::digits;
+    /// assert_eq!(digits("1234元"), Ok((1234, "元")));
+    /// assert_eq!(digits("1,234,567元"), Ok((1234567, "元")));
+    /// assert_eq!(digits("1234,5678元"), Ok((12345678, "元")));
+    /// assert_eq!(digits("1,2345,678"), Ok((12345, ",678")));
+    /// assert!(digits("一万").is_err());
+    /// ```
+    pub rule digits() -> (Number, &'input str)
+        = n:grouped_number() s:$([_]*)
+        { (n, s) }
+
+    /// Like [`digits`], but the digits are not grouped
+    ///
+    /// # Examples
+    /// ```
+    /// # use zh_num::parser::plain_digits;
+    /// assert_eq!(plain_digits("1234元"), Ok((1234, "元")));
+    /// assert_eq!(plain_digits("2023,2024年"), Ok((2023, ",2024年")));
+    /// ```
+    pub rule plain_digits() -> (Number, &'input str)
+        = n:ascii_number() s:$([_]*)
+        { (n, s) }
+
+    /// Parse zh nums into a syntax tree, return the tree and rest text
+    ///
+    /// Accepts the same zh nums as [`number`], but not ASCII digits
+    ///
+    /// # Examples
+    /// ```
+    /// # use zh_num::parser::ast_number;
+    /// let (ast, rest) = ast_number("一万零八十六章").unwrap();
+    /// assert_eq!(ast.value(), Some(10086));
+    /// assert_eq!(ast.to_string(), "1×10000 + 8×10 + 6");
+    /// assert_eq!(rest, "章");
+    /// ```
+    pub rule ast_number() -> (ast::YiNumber, &'input str)
+        = n:yi_ast() s:$([_]*)
+        { (n, s) }
+
+    /// Parse zh nums into any [`ZhInt`], return parsed number and rest text
+    ///
+    /// Negative numbers start with `负` or `-`
+    ///
+    /// # Examples
+    /// ```
+    /// # use zh_num::parser::integer;
+    /// assert_eq!(integer::<i32>("负一万零十三章"), Ok((-10013, "章")));
+    /// assert_eq!(integer::<u8>("二百五十五"), Ok((255, "")));
+    /// assert!(integer::<u8>("二百五十六").is_err());
+    /// assert!(integer::<u8>("负一").is_err());
+    /// assert_eq!(integer::<i128>("-1"), Ok((-1, "")));
+    /// assert_eq!(integer::<u128>("一亿亿亿"), Ok((10u128.pow(24), "")));
+    /// ```
+    pub rule integer<T: ZhInt>() -> (T, &'input str)
+        = neg:(("负" / "-") { true })?
+          n:(ascii_wide() / yi_wide())
+          s:$([_]*)
+        {?
+            T::from_parts(neg.unwrap_or_default(), n)
+                .map(|n| (n, s))
+                .ok_or("valid-number")
+        }
+
+    rule exponent() -> u32
+        = n:raw_number() {? u32::try_from(n).map_err(|_| "valid-exponent") }
+    rule pow() -> Number
+        = b:raw_number() "的" e:exponent() "次" ("方" / "幂")
+        {? b.checked_pow(e).ok_or("valid-number") }
+        / b:ascii_number() "^" e:exponent()
+        {? b.checked_pow(e).ok_or("valid-number") }
+    rule times() = "乘以" / "乘" / "×" / "*"
+
+    /// Parse power notation, return parsed number and rest text
+    ///
+    /// Like `十的八次方`, `三乘以十的五次方` or `2×10^8`
+    ///
+    /// # Examples
+    /// ```
+    /// # use zh_num::parser::power_number;
+    /// assert_eq!(power_number("十的八次方元"), Ok((1_0000_0000, "元")));
+    /// assert_eq!(power_number("二的十次幂"), Ok((1024, "")));
+    /// assert_eq!(power_number("三乘十的五次方"), Ok((30_0000, "")));
+    /// assert_eq!(power_number("三乘以十的五次方"), Ok((30_0000, "")));
+    /// assert_eq!(power_number("2×10^8"), Ok((2_0000_0000, "")));
+    /// assert!(power_number("十的二十次方").is_err());
+    /// assert!(power_number("一万").is_err());
+    /// ```
+    pub rule power_number() -> (Number, &'input str)
+        = n:(
+            m:raw_number() times() p:pow()
+            {? m.checked_mul(p).ok_or("valid-number") }
+            / pow())
+        s:$([_]*)
+        { (n, s) }
+
+    rule k_unit() -> Number
+        = ['十' | '拾'] { 10 }
+        / ['百' | '佰' | '陌'] { 100 }
+        / ['千' | '仟' | '阡'] { 1000 }
+    rule round_number() -> Number
+        = u:k_unit()? w:power_num()?
+        {?
+            match (u, w) {
+                (None, None) => Err("round-number"),
+                _ => Ok(u.unwrap_or(1) * w.unwrap_or(1)),
+            }
+        }
+    rule approx() -> Approx
+        = q:("上" { Qualifier::AtLeast } / "近" { Qualifier::Nearly })
+          n:(round_number() / raw_number())
+        {? Approx::new(n, q).ok_or("valid-number") }
+        / q:("几" { Qualifier::Several } / "数" { Qualifier::Many })
+          n:round_number()
+        {? Approx::new(n, q).ok_or("valid-number") }
+        / n:(raw_number() / round_number()) "左右"
+        {? Approx::new(n, Qualifier::Around).ok_or("valid-number") }
+        / n:(raw_number() / round_number())
+          q:(
+            "多" { Qualifier::More }
+            / "余" { Qualifier::Over }
+            / "几" { Qualifier::Odd }
+            / "来" { Qualifier::About })
+          w:power_num()?
+        {?
+            n.checked_mul(w.unwrap_or(1))
+                .and_then(|n| Approx::new(n, q))
+                .ok_or("valid-number")
+        }
+
+    /// Parse approximate quantities, return parsed [`Approx`] and rest text
+    ///
+    /// # Examples
+    /// ```
+    /// # use zh_num::{parser::approx_number, Qualifier};
+    /// let (approx, rest) = approx_number("三千多人").unwrap();
+    /// assert_eq!(approx.range, 3001..=3999);
+    /// assert_eq!(approx.qualifier, Qualifier::More);
+    /// assert_eq!(rest, "人");
+    ///
+    /// assert_eq!(approx_number("十几个").unwrap().0.range, 11..=19);
+    /// assert_eq!(approx_number("上万元").unwrap().0.range, 10000..=99999);
+    /// assert_eq!(approx_number("数十").unwrap().0.range, 20..=99);
+    /// assert!(approx_number("三千").is_err());
+    /// ```
+    pub rule approx_number() -> (Approx, &'input str)
+        = a:approx() s:$([_]*)
+        { (a, s) }
+
+    rule range_sep() = "到" / "至" / "~" / "～" / "-" / "—" / "–"
+    rule range_digit() -> Number
+        = n:one_num(0) {? if n != 0 { Ok(n) } else { Err("nonzero-digit") } }
+    rule range() -> RangeInclusive<Number>
+        // 三到五万, the unit is shared
+        = a:range_digit() range_sep() b:range_digit() w:round_number()
+          !one_num(0)
+        {?
+            if a >= b || w < 100 { return Err("shared-unit") }
+            Ok(a.checked_mul(w).ok_or("valid-number")?..=b * w)
+        }
+        // 十五到二十万, the trailing magnitude is shared
+        / a:k_number() range_sep() b:k_number() ws:power_num()+ !one_num(0)
+        {?
+            let w = ws.into_iter()
+                .try_fold(1 as Number, Number::checked_mul)
+                .ok_or("valid-number")?;
+            let value = |k: ast::KNumber| k.value().checked_mul(w).ok_or("valid-number");
+            let (a, b) = (value(a)?, value(b)?);
+            if a < b { Ok(a..=b) } else { Err("valid-range") }
+        }
+        / a:raw_number() range_sep() b:raw_number()
+        {? if a < b { Ok(a..=b) } else { Err("valid-range") } }
+        // 十五六 一万五六千 七八十 三五
+        / p:$((one_num(0)? (k_unit() / power_num()))*)
+          a:range_digit() b:range_digit()
+          k:k_unit()? w:power_num()?
+        {?
+            if b != a + 1 && (a, b) != (3, 5) { return Err("adjacent-digits") }
+            let k = k.unwrap_or(1);
+            let p = match p {
+                "" => 0,
+                p => number(p).map_err(|_| "valid-number")?.0,
+            };
+            // the digits are of the next lower unit of the prefix
+            if p != 0 && place_value(p) != k * 10 { return Err("next-unit") }
+            let value = |d: Number| {
+                p.checked_add(d * k)
+                    .and_then(|n| n.checked_mul(w.unwrap_or(1)))
+                    .ok_or("valid-number")
+            };
+            Ok(value(a)?..=value(b)?)
+        }
+
+    /// Parse ranges, return parsed range and rest text
+    ///
+    /// Like `三到五`, `十至二十`, `3~5`, or adjacent digits `三五` `七八十`
+    ///
+    /// # Examples
+    /// ```
+    /// # use zh_num::parser::range_number;
+    /// assert_eq!(range_number("三到五天"), Ok((3..=5, "天")));
+    /// assert_eq!(range_number("十至二十"), Ok((10..=20, "")));
+    /// assert_eq!(range_number("3~5"), Ok((3..=5, "")));
+    /// assert_eq!(range_number("三到五万"), Ok((30000..=50000, "")));
+    /// assert_eq!(range_number("十五到二十万"), Ok((150000..=200000, "")));
+    /// assert_eq!(range_number("三五个"), Ok((3..=5, "个")));
+    /// assert_eq!(range_number("七八十"), Ok((70..=80, "")));
+    /// assert_eq!(range_number("十五六"), Ok((15..=16, "")));
+    /// assert!(range_number("五到三").is_err());
+    /// ```
+    pub rule range_number() -> (RangeInclusive<Number>, &'input str)
+        = r:range() s:$([_]*)
+        { (r, s) }
+
+    /// Parse Suzhou numerals, return parsed number and rest text
+    ///
+    /// Digits may be followed by the magnitude of the first digit
+    ///
+    /// # Examples
+    /// ```
+    /// # use zh_num::parser::suzhou_number;
+    /// assert_eq!(suzhou_number("〤〥〇〢千元"), Ok((4502, "元")));
+    /// assert_eq!(suzhou_number("〤〥千元"), Ok((4500, "元")));
+    /// assert_eq!(suzhou_number("〡一〢三元"), Ok((1123, "元")));
+    /// assert_eq!(suzhou_number("〢十万"), Ok((200000, "")));
+    /// assert!(suzhou_number("〤〥〇〢十").is_err());
+    /// ```
+    pub rule suzhou_number() -> (Number, &'input str)
+        = &['〇' | '〡'..='〩'] ds:suzhou_digit()+ pow:unit_pow()? s:$([_]*)
+        {?
+            let num = ds.iter()
+                .try_fold(0 as Number, |acc, &d| {
+                    acc.checked_mul(10)?.checked_add(d)
+                })
+                .ok_or("valid-number")?;
+            let Some(pow) = pow else { return Ok((num, s)) };
+            let zeros = (pow + 1).checked_sub(ds.len() as u32)
+                .ok_or("valid-magnitude")?;
+            Number::checked_pow(10, zeros)
+                .and_then(|p| num.checked_mul(p))
+                .map(|num| (num, s))
+                .ok_or("valid-number")
+        }
+
+    /// Parse counting rod numerals, return parsed number and rest text
+    ///
+    /// Zero is `〇` or a blank between digits,
+    /// vertical and horizontal forms are accepted at any position
+    ///
+    /// # Examples
+    /// ```
+    /// # use zh_num::parser::rod_number;
+    /// assert_eq!(rod_number("\u{1D36E}\u{1D366}〇\u{1D367}年"), Ok((6708, "年")));
+    /// assert_eq!(rod_number("\u{1D36E}\u{1D366} \u{1D367} 年"), Ok((6708, " 年")));
+    /// assert_eq!(rod_number("\u{1D360}〇〇"), Ok((100, "")));
+    /// ```
+    pub rule rod_number() -> (Number, &'input str)
+        = &(rod_digit() / "〇") ds:(rod_digit() / rod_zero())+ s:$([_]*)
+        {?
+            ds.into_iter()
+                .try_fold(0 as Number, |acc, d| {
+                    acc.checked_mul(10)?.checked_add(d)
+                })
+                .map(|num| (num, s))
+                .ok_or("valid-number")
+        }
+
+    rule code_sep() = [' ' | '\u{3000}' | '-' | '—']
+    rule code_digit() -> char
+        = ['零' | '〇'] { '0' }
+        / n:one_num(0) { char::from_digit(n as u32, 10).unwrap() }
+        / ch:[ch if digit_value(ch).is_some()] {
+            char::from_digit(digit_value(ch).unwrap(), 10).unwrap()
+        }
+
+    /// Parse phone numbers or codes read digit by digit,
+    /// return ASCII digits and rest text
+    ///
+    /// Spaces and dashes are allowed between digits
+    ///
+    /// # Examples
+    /// ```
+    /// # use zh_num::parser::code_digits;
+    /// assert_eq!(
+    ///     code_digits("幺三八 幺二三四-五六七八号"),
+    ///     Ok(("13812345678".into(), "号")),
+    /// );
+    /// assert_eq!(code_digits("零幺零 "), Ok(("010".into(), " ")));
+    /// ```
+    pub rule code_digits() -> (String, &'input str)
+        = first:code_digit() rest:(code_sep()* d:code_digit() { d })* s:$([_]*)
+        {
+            let digits = [first].into_iter()
+                .chain(rest)
+                .collect();
+            (digits, s)
+        }
+
     /// Parse hard zh nums, return parsed number and rest text
     ///
     /// # Examples
@@ -68,34 +507,137 @@ peg::parser!(pub grammar parser() for str {
     /// assert_eq!(hard_number("一零零十三章"), Ok((10013, "章")));
     /// assert_eq!(hard_number("零零零章"), Ok((0, "章")));
     /// assert_eq!(hard_number("百零零章"), Ok((100, "章")));
+    /// assert_eq!(hard_number("一〇〇八六章"), Ok((10086, "章")));
     /// ```
     pub rule hard_number() -> (Number, &'input str)
         = nums:(
-            "零" { 0 }
+            ['零' | '〇'] { 0 }
             / ['十' | '百' | '千' | '万' | '亿'] { 1 }
             / one_num(0))+
         s:$([_]*)
-        {
-            let num = nums.into_iter()
-                .fold(0, |acc, num| acc * 10 + num);
-            (num, s)
+        {?
+            nums.into_iter()
+                .try_fold(0 as Number, |acc, num| {
+                    acc.checked_mul(10)?.checked_add(num)
+                })
+                .map(|num| (num, s))
+                .ok_or("valid-number")
         }
 });
 
-struct FmtNum<'a, C>(Number, Cell<Option<&'a mut Option<bool>>>, C);
+/// Get value of ASCII or full-width digit
+///
+/// # Examples
+/// ```
+/// # use zh_num::digit_value;
+/// assert_eq!(digit_value('7'), Some(7));
+/// assert_eq!(digit_value('７'), Some(7));
+/// assert_eq!(digit_value('七'), None);
+/// ```
+pub fn digit_value(ch: char) -> Option<u32> {
+    match ch {
+        '0'..='9' => Some(ch as u32 - '0' as u32),
+        '０'..='９' => Some(ch as u32 - '０' as u32),
+        _ => None,
+    }
+}
+
+/// Is a char of zh numbers, ASCII or full-width digits
+///
+/// # Examples
+/// ```
+/// # use zh_num::is_numeral;
+/// assert!(is_numeral('万'));
+/// assert!(is_numeral('７'));
+/// assert!(!is_numeral('章'));
+/// ```
+pub fn is_numeral(ch: char) -> bool {
+    digit_value(ch).is_some() || matches!(ch,
+        '零' | '〇'
+        | '一' | '壹' | '弌' | '幺'
+        | '二' | '贰' | '弍' | '两'
+        | '三' | '叁' | '弎'
+        | '四' | '肆'
+        | '五' | '伍'
+        | '六' | '陆'
+        | '七' | '柒'
+        | '八' | '捌'
+        | '九' | '玖'
+        | '十' | '拾'
+        | '百' | '佰' | '陌'
+        | '千' | '仟' | '阡'
+        | '万' | '亿'
+    )
+}
+
+/// Place value of the lowest non-zero digit, e.g `100` of `3500`
+pub(crate) fn place_value(num: Number) -> Number {
+    let mut unit = 1;
+    while num != 0 && (num / unit).is_multiple_of(10) {
+        unit *= 10;
+    }
+    unit
+}
+
+/// Parse ASCII or full-width digits, like [`str::parse`]
+///
+/// # Examples
+/// ```
+/// # use zh_num::parse_digits;
+/// assert_eq!(parse_digits("1234"), Ok(1234));
+/// assert_eq!(parse_digits("１２３４"), Ok(1234));
+/// assert_eq!(parse_digits("１2３4"), Ok(1234));
+/// assert!(parse_digits("").is_err());
+/// ```
+pub fn parse_digits(s: &str) -> Result<Number, ParseIntError> {
+    parse_digits_as(s)
+}
+
+fn parse_digits_as<T>(s: &str) -> Result<T, ParseIntError>
+where T: FromStr<Err = ParseIntError>,
+{
+    if s.is_ascii() {
+        return s.parse();
+    }
+    s.chars()
+        .map(|ch| digit_value(ch)
+            .and_then(|d| char::from_digit(d, 10))
+            .unwrap_or(ch))
+        .collect::<String>()
+        .parse()
+}
+
+struct FmtNum<'a, C> {
+    num: u128,
+    sp: Cell<Option<&'a mut Option<bool>>>,
+    opts: FmtOptions,
+    /// Followed by `万` or `亿`
+    big: bool,
+    cfg: PhantomData<C>,
+}
+impl<'a, C: NumCfg> FmtNum<'a, C> {
+    fn new(
+        num: u128,
+        sp: &'a mut Option<bool>,
+        opts: FmtOptions,
+        big: bool,
+    ) -> Self {
+        Self { num, sp: Cell::new(Some(sp)), opts, big, cfg: PhantomData }
+    }
+}
 impl<C> fmt::Display for FmtNum<'_, C>
 where C: NumCfg,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let num = self.0;
-        let sp = self.1.take().unwrap();
+        let Self { num, opts, big, cfg: _, .. } = *self;
+        let sp = self.sp.take().unwrap();
         match num {
-            0..=9999 => C::unit(num, sp, f),
+            0..=9999 => C::unit(num, sp, opts, big, f),
             1_0000..=9999_9999 => {
-                C::concat_unit(num, sp, 1_0000, C::N1_0000, f)
+                C::concat_unit(num, sp, opts, big, 1_0000, C::N1_0000, f)
             },
-            1_0000_0000..=Number::MAX => {
-                C::concat_unit(num, sp, 1_0000_0000, C::N1_0000_0000, f)
+            1_0000_0000..=u128::MAX => {
+                C::concat_unit(num, sp, opts, big, 1_0000_0000, C::N1_0000_0000, f)
             },
         }
     }
@@ -107,6 +649,7 @@ trait NumCfg: Default + Sized {
     const N1000: char;
     const N1_0000: char;
     const N1_0000_0000: char;
+    const LIANG: Option<char> = None;
 
     const K_POWS: [Option<char>; 4] = [
         None,
@@ -122,22 +665,34 @@ trait NumCfg: Default + Sized {
         }
     }
 
+    fn zero(opts: FmtOptions) -> char {
+        if opts.circle_zero { '〇' } else { Self::DIGITS[0] }
+    }
+
     fn unit(
-        num: Number,
+        num: u128,
         sp: &mut Option<bool>,
+        opts: FmtOptions,
+        big: bool,
         f: &mut fmt::Formatter<'_>,
     ) -> fmt::Result {
         assert!(num < 10000, "{num}");
 
         for (pow_d, p) in (0..4).zip(Self::K_POWS).rev() {
-            let digit = num / Number::pow(10, pow_d) % 10;
-            let digit_ch = Self::one(digit);
+            let digit = (num / u128::pow(10, pow_d) % 10) as Number;
+            let digit_ch = match (digit, Self::LIANG) {
+                (2, Some(liang)) if opts.liang
+                    && (pow_d >= 2 || big && num == 2) => liang,
+                _ => Self::one(digit),
+            };
             if digit == 0 {
                 if let Some(x) = sp { *x = true }
                 continue;
             }
-            if let Some(true) = sp { write!(f, "{}", Self::DIGITS[0])? }
-            if !(sp.is_none() && digit == 1 && p == Some('十')) {
+            if let Some(true) = sp { write!(f, "{}", Self::zero(opts))? }
+            if !(sp.is_none() && digit == 1 && p == Some(Self::N10))
+                || opts.leading_one
+            {
                 write!(f, "{digit_ch}")?;
             }
             if let Some(p) = p {
@@ -149,30 +704,54 @@ trait NumCfg: Default + Sized {
     }
 
     fn concat_unit(
-        num: Number,
+        num: u128,
         sp: &mut Option<bool>,
-        pow_i: Number,
+        opts: FmtOptions,
+        big: bool,
+        pow_i: u128,
         pow_ch: char,
         f: &mut fmt::Formatter<'_>,
     ) -> fmt::Result {
         let [a, b] = [num / pow_i, num % pow_i];
-        write!(f, "{}", FmtNum(a, Cell::new(sp.into()), Self::default()))?;
+        write!(f, "{}", FmtNum::<Self>::new(a, sp, opts, true))?;
         write!(f, "{pow_ch}")?;
-        write!(f, "{}", FmtNum(b, Cell::new(sp.into()), Self::default()))?;
+        write!(f, "{}", FmtNum::<Self>::new(b, sp, opts, big))?;
         Ok(())
     }
 
-    fn fmt_zh_num(num: Number, mut f: impl fmt::Write) -> fmt::Result {
+    fn fmt_zh_num(
+        num: u128,
+        opts: FmtOptions,
+        mut f: impl fmt::Write,
+    ) -> fmt::Result {
         if num == 0 {
-            return write!(f, "{}", Self::one(0));
+            return write!(f, "{}", Self::zero(opts));
         }
-        write!(f, "{}", FmtNum(num, Cell::new(Some(&mut None)), Self::default()))
+        write!(f, "{}", FmtNum::<Self>::new(num, &mut None, opts, false))
+    }
+
+    /// Write units of `10^pow`, e.g `十万` `万亿`
+    fn fmt_magnitude(pow: u32, mut f: impl fmt::Write) -> fmt::Result {
+        if let Some(p) = Self::K_POWS[pow as usize % 4] {
+            f.write_char(p)?;
+        }
+        if pow % 8 >= 4 {
+            f.write_char(Self::N1_0000)?;
+        }
+        for _ in 0..pow / 8 {
+            f.write_char(Self::N1_0000_0000)?;
+        }
+        Ok(())
     }
 }
 #[derive(Debug, Default)]
 struct LowerNum;
 #[derive(Debug, Default)]
 struct UpperNum;
+#[derive(Debug, Default)]
+struct SuzhouNum;
+#[derive(Debug, Default)]
+struct RodNum;
 impl NumCfg for LowerNum {
     const DIGITS: [char; 10] = [
         '零',
@@ -191,6 +770,7 @@ impl NumCfg for LowerNum {
     const N1000: char = '千';
     const N1_0000: char = '万';
     const N1_0000_0000: char = '亿';
+    const LIANG: Option<char> = Some('两');
 }
 impl NumCfg for UpperNum {
     const DIGITS: [char; 10] = [
@@ -211,19 +791,268 @@ impl NumCfg for UpperNum {
     const N1_0000: char = '万';
     const N1_0000_0000: char = '亿';
 }
+impl NumCfg for SuzhouNum {
+    const DIGITS: [char; 10] = [
+        '〇',
+        '〡',
+        '〢',
+        '〣',
+        '〤',
+        '〥',
+        '〦',
+        '〧',
+        '〨',
+        '〩',
+    ];
+    const N10: char = '十';
+    const N100: char = '百';
+    const N1000: char = '千';
+    const N1_0000: char = '万';
+    const N1_0000_0000: char = '亿';
+}
+impl NumCfg for RodNum {
+    const DIGITS: [char; 10] = [
+        '〇',
+        '\u{1D360}',
+        '\u{1D361}',
+        '\u{1D362}',
+        '\u{1D363}',
+        '\u{1D364}',
+        '\u{1D365}',
+        '\u{1D366}',
+        '\u{1D367}',
+        '\u{1D368}',
+    ];
+    const N10: char = '十';
+    const N100: char = '百';
+    const N1000: char = '千';
+    const N1_0000: char = '万';
+    const N1_0000_0000: char = '亿';
+}
+impl RodNum {
+    const TENS_DIGITS: [char; 10] = [
+        '〇',
+        '\u{1D369}',
+        '\u{1D36A}',
+        '\u{1D36B}',
+        '\u{1D36C}',
+        '\u{1D36D}',
+        '\u{1D36E}',
+        '\u{1D36F}',
+        '\u{1D370}',
+        '\u{1D371}',
+    ];
+
+    /// Write digits, odd positions use the tens (horizontal) forms
+    fn fmt_rod(num: Number, mut f: impl fmt::Write) -> fmt::Result {
+        for pow_d in (0..=num.checked_ilog10().unwrap_or(0)).rev() {
+            let digit = num / Number::pow(10, pow_d) % 10;
+            let ch = if pow_d % 2 == 0 {
+                Self::one(digit)
+            } else {
+                Self::TENS_DIGITS[digit as usize]
+            };
+            f.write_char(ch)?;
+        }
+        Ok(())
+    }
+}
+impl SuzhouNum {
+    /// Write significant digits, then the magnitude of the first digit
+    ///
+    /// Adjacent `1..=3` alternate between vertical and horizontal forms
+    fn fmt_suzhou(num: Number, mut f: impl fmt::Write) -> fmt::Result {
+        if num == 0 {
+            return f.write_char(Self::one(0));
+        }
+        let pow = num.ilog10();
+        let mut sig = num;
+        while sig.is_multiple_of(10) { sig /= 10 }
+
+        let mut horizontal = false;
+        for pow_d in (0..=sig.ilog10()).rev() {
+            let digit = sig / Number::pow(10, pow_d) % 10;
+            let ch = match digit {
+                1..=3 if horizontal => LowerNum::one(digit),
+                _ => Self::one(digit),
+            };
+            horizontal = (1..=3).contains(&digit) && !horizontal;
+            f.write_char(ch)?;
+        }
+        Self::fmt_magnitude(pow, f)
+    }
+}
+
+/// Options of zh numbers formatting
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct FmtOptions {
+    /// Keep `一` before the leading `十`, e.g `一十二` instead of `十二`
+    pub leading_one: bool,
+    /// Use `两` for `2` before `百` `千` and a single `2` before `万` `亿`,
+    /// e.g `两千两百` `两万`, ignored when upper
+    pub liang: bool,
+    /// Use `〇` instead of `零`
+    pub circle_zero: bool,
+}
+impl FmtOptions {
+    /// Options of [`fmt_zh_num_upper`], keep `壹` before `拾`
+    pub const UPPER: Self = Self {
+        leading_one: true,
+        liang: false,
+        circle_zero: false,
+    };
+}
+
+/// Display columns of a char, CJK chars are double width
+fn char_width(ch: char) -> usize {
+    match ch {
+        '\u{200B}'..='\u{200D}' | '\u{2060}' | '\u{FEFF}' => 0,
+        '\u{1100}'..='\u{115F}'
+        | '\u{2E80}'..='\u{303E}'
+        | '\u{3041}'..='\u{33FF}'
+        | '\u{3400}'..='\u{4DBF}'
+        | '\u{4E00}'..='\u{9FFF}'
+        | '\u{A000}'..='\u{A4CF}'
+        | '\u{AC00}'..='\u{D7A3}'
+        | '\u{F900}'..='\u{FAFF}'
+        | '\u{FE30}'..='\u{FE4F}'
+        | '\u{FF00}'..='\u{FF60}'
+        | '\u{FFE0}'..='\u{FFE6}'
+        | '\u{1D360}'..='\u{1D37F}'
+        | '\u{20000}'..='\u{3FFFD}' => 2,
+        _ => 1,
+    }
+}
+
+/// Display columns of a str in terminals, CJK chars are double width
+///
+/// # Examples
+/// ```
+/// # use zh_num::{display_width, to_zh_num};
+/// assert_eq!(display_width("1234"), 4);
+/// assert_eq!(display_width(&to_zh_num(1234)), 14);
+/// assert_eq!(display_width("１２"), 4);
+/// assert_eq!(display_width("第3章"), 5);
+/// ```
+pub fn display_width(s: &str) -> usize {
+    s.chars().map(char_width).sum()
+}
+
+/// Call `write` with the width, fill and alignment of `f`,
+/// the width is measured in display columns and right aligned by default
+pub(crate) fn fmt_padded(
+    f: &mut fmt::Formatter<'_>,
+    write: impl FnOnce(&mut dyn fmt::Write) -> fmt::Result,
+) -> fmt::Result {
+    use fmt::Write;
+
+    let Some(width) = f.width() else { return write(f) };
+    let mut s = String::new();
+    write(&mut s)?;
+    let pad = width.saturating_sub(display_width(&s));
+    let (pre, post) = match f.align() {
+        Some(fmt::Alignment::Left) => (0, pad),
+        Some(fmt::Alignment::Center) => (pad / 2, pad - pad / 2),
+        Some(fmt::Alignment::Right) | None => (pad, 0),
+    };
+    let fill = f.fill();
+    for _ in 0..pre { f.write_char(fill)? }
+    f.write_str(&s)?;
+    for _ in 0..post { f.write_char(fill)? }
+    Ok(())
+}
 
 /// [`to_zh_num`] write to [`Write`] impl
 ///
 /// [`Write`]: fmt::Write
 pub fn fmt_zh_num(num: Number, f: impl fmt::Write) -> fmt::Result {
-    LowerNum::fmt_zh_num(num, f)
+    LowerNum::fmt_zh_num(num.into(), FmtOptions::default(), f)
 }
 
 /// [`to_zh_num_upper`] write to [`Write`] impl
 ///
 /// [`Write`]: fmt::Write
 pub fn fmt_zh_num_upper(num: Number, f: impl fmt::Write) -> fmt::Result {
-    UpperNum::fmt_zh_num(num, f)
+    UpperNum::fmt_zh_num(num.into(), FmtOptions::UPPER, f)
+}
+
+/// Like [`fmt_zh_num`], but for any [`ZhInt`], negative numbers start with `负`
+///
+/// # Examples
+/// ```
+/// # use zh_num::fmt_zh_int;
+/// let mut s = String::new();
+/// fmt_zh_int(-10086i32, &mut s).unwrap();
+/// assert_eq!(s, "负一万零八十六");
+/// ```
+pub fn fmt_zh_int<T: ZhInt>(num: T, mut f: impl fmt::Write) -> fmt::Result {
+    let (negative, abs) = num.to_parts();
+    if negative { f.write_str("负")? }
+    LowerNum::fmt_zh_num(abs, FmtOptions::default(), f)
+}
+
+/// Like [`fmt_zh_num_upper`], but for any [`ZhInt`],
+/// negative numbers start with `负`
+pub fn fmt_zh_int_upper<T: ZhInt>(num: T, mut f: impl fmt::Write) -> fmt::Result {
+    let (negative, abs) = num.to_parts();
+    if negative { f.write_str("负")? }
+    UpperNum::fmt_zh_num(abs, FmtOptions::UPPER, f)
+}
+
+/// [`to_zh_num_with`] write to [`Write`] impl
+///
+/// [`Write`]: fmt::Write
+pub fn fmt_zh_num_with(
+    num: Number,
+    opts: FmtOptions,
+    f: impl fmt::Write,
+) -> fmt::Result {
+    LowerNum::fmt_zh_num(num.into(), opts, f)
+}
+
+/// [`to_zh_num_upper_with`] write to [`Write`] impl
+///
+/// [`Write`]: fmt::Write
+pub fn fmt_zh_num_upper_with(
+    num: Number,
+    opts: FmtOptions,
+    f: impl fmt::Write,
+) -> fmt::Result {
+    UpperNum::fmt_zh_num(num.into(), opts, f)
+}
+
+/// Convert number to zh numbers with options
+///
+/// # Examples
+/// ```
+/// # use zh_num::{to_zh_num_with, FmtOptions};
+/// let opts = FmtOptions { liang: true, ..Default::default() };
+/// assert_eq!(to_zh_num_with(22000, opts), "两万两千");
+///
+/// let opts = FmtOptions { leading_one: true, ..Default::default() };
+/// assert_eq!(to_zh_num_with(12, opts), "一十二");
+///
+/// let opts = FmtOptions { circle_zero: true, ..Default::default() };
+/// assert_eq!(to_zh_num_with(10086, opts), "一万〇八十六");
+/// ```
+pub fn to_zh_num_with(num: Number, opts: FmtOptions) -> String {
+    let mut s = String::new();
+    fmt_zh_num_with(num, opts, &mut s).unwrap();
+    s
+}
+
+/// Convert number to upper zh numbers with options
+///
+/// # Examples
+/// ```
+/// # use zh_num::{to_zh_num_upper_with, FmtOptions};
+/// assert_eq!(to_zh_num_upper_with(12, FmtOptions::UPPER), "壹拾贰");
+/// assert_eq!(to_zh_num_upper_with(12, FmtOptions::default()), "拾贰");
+/// ```
+pub fn to_zh_num_upper_with(num: Number, opts: FmtOptions) -> String {
+    let mut s = String::new();
+    fmt_zh_num_upper_with(num, opts, &mut s).unwrap();
+    s
 }
 
 /// Convert number to zh numbers
@@ -248,22 +1077,58 @@ pub fn to_zh_num_upper(num: Number) -> String {
     ZhNumUpper(num).to_string()
 }
 
-/// [`fmt_zh_num`] wrapper, impl [`Display`]
+/// Convert any [`ZhInt`] to zh numbers
+///
+/// # Examples
+/// ```
+/// # use zh_num::to_zh_int;
+/// assert_eq!(to_zh_int(-12i8), "负十二");
+/// assert_eq!(to_zh_int(300u16), "三百");
+/// assert_eq!(to_zh_int(10u128.pow(24)), "一亿亿亿");
+/// ```
+pub fn to_zh_int<T: ZhInt>(num: T) -> String {
+    ZhNum(num).to_string()
+}
+
+/// Convert any [`ZhInt`] to upper zh numbers
+///
+/// # Examples
+/// ```
+/// # use zh_num::to_zh_int_upper;
+/// assert_eq!(to_zh_int_upper(-12i64), "负壹拾贰");
+/// ```
+pub fn to_zh_int_upper<T: ZhInt>(num: T) -> String {
+    ZhNumUpper(num).to_string()
+}
+
+/// [`fmt_zh_int`] wrapper, impl [`Display`]
+///
+/// Width, fill and alignment are measured in display columns,
+/// `{:#}` is the same as [`ZhNumUpper`]
 ///
 /// # Examples
 /// ```
 /// # use zh_num::{ZhNum, to_zh_num};
 /// assert_eq!(ZhNum(83362).to_string(), to_zh_num(83362));
+/// assert_eq!(ZhNum(-5i32).to_string(), "负五");
+/// assert_eq!(format!("{:>6}|", ZhNum(12)), "  十二|");
+/// assert_eq!(format!("{:-<6}|", ZhNum(12)), "十二--|");
+/// assert_eq!(format!("{:^7}|", ZhNum(12)), " 十二  |");
+/// assert_eq!(format!("{:#}", ZhNum(12)), "壹拾贰");
 /// ```
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub struct ZhNum(pub Number);
-impl Display for ZhNum {
+pub struct ZhNum<T = Number>(pub T);
+impl<T: ZhInt> Display for ZhNum<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        fmt_zh_num(self.0, f)
+        if f.alternate() {
+            fmt_padded(f, |w| fmt_zh_int_upper(self.0, w))
+        } else {
+            fmt_padded(f, |w| fmt_zh_int(self.0, w))
+        }
     }
 }
 
-/// [`fmt_zh_num_upper`] wrapper, impl [`Display`]
+/// [`fmt_zh_int_upper`] wrapper, impl [`Display`]
 ///
 /// # Examples
 /// ```
@@ -271,10 +1136,448 @@ impl Display for ZhNum {
 /// assert_eq!(ZhNumUpper(83362).to_string(), to_zh_num_upper(83362));
 /// ```
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub struct ZhNumUpper(pub Number);
-impl Display for ZhNumUpper {
+pub struct ZhNumUpper<T = Number>(pub T);
+impl<T: ZhInt> Display for ZhNumUpper<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_padded(f, |w| fmt_zh_int_upper(self.0, w))
+    }
+}
+
+/// Min exponent of [`to_zh_num_power`], e.g `十的四次方`
+pub const POWER_MIN_EXP: u32 = 4;
+
+/// [`to_zh_num_power`] write to [`Write`] impl
+///
+/// [`Write`]: fmt::Write
+pub fn fmt_zh_num_power(num: Number, f: impl fmt::Write) -> fmt::Result {
+    fmt_zh_num_power_with(num, POWER_MIN_EXP, f)
+}
+
+/// [`to_zh_num_power_with`] write to [`Write`] impl
+///
+/// [`Write`]: fmt::Write
+pub fn fmt_zh_num_power_with(
+    num: Number,
+    min_exp: u32,
+    mut f: impl fmt::Write,
+) -> fmt::Result {
+    let mut mantissa = num;
+    let mut exp = 0;
+    while mantissa != 0 && mantissa.is_multiple_of(10) {
+        mantissa /= 10;
+        exp += 1;
+    }
+    if exp == 0 || exp < min_exp {
+        return fmt_zh_num(num, f);
+    }
+    if mantissa != 1 {
+        fmt_zh_num(mantissa, &mut f)?;
+        f.write_str("乘")?;
+    }
+    f.write_str("十的")?;
+    fmt_zh_num(exp.into(), &mut f)?;
+    f.write_str("次方")
+}
+
+/// Convert large round number to power notation, like `三乘十的八次方`,
+/// numbers with less than [`POWER_MIN_EXP`] trailing zeros
+/// are the same as [`to_zh_num`]
+///
+/// # Examples
+/// ```
+/// # use zh_num::to_zh_num_power;
+/// assert_eq!(to_zh_num_power(1_0000_0000), "十的八次方");
+/// assert_eq!(to_zh_num_power(3_0000_0000), "三乘十的八次方");
+/// assert_eq!(to_zh_num_power(25_0000), "二十五乘十的四次方");
+/// assert_eq!(to_zh_num_power(123), "一百二十三");
+/// assert_eq!(to_zh_num_power(120), "一百二十");
+/// ```
+pub fn to_zh_num_power(num: Number) -> String {
+    ZhNumPower(num).to_string()
+}
+
+/// Like [`to_zh_num_power`], but the min exponent is `min_exp`
+///
+/// # Examples
+/// ```
+/// # use zh_num::to_zh_num_power_with;
+/// assert_eq!(to_zh_num_power_with(3000, 3), "三乘十的三次方");
+/// assert_eq!(to_zh_num_power_with(3000, 4), "三千");
+/// assert_eq!(to_zh_num_power_with(10, 0), "十的一次方");
+/// ```
+pub fn to_zh_num_power_with(num: Number, min_exp: u32) -> String {
+    let mut s = String::new();
+    fmt_zh_num_power_with(num, min_exp, &mut s).unwrap();
+    s
+}
+
+/// [`fmt_zh_num_power`] wrapper, impl [`Display`]
+///
+/// # Examples
+/// ```
+/// # use zh_num::{ZhNumPower, to_zh_num_power};
+/// assert_eq!(ZhNumPower(83000).to_string(), to_zh_num_power(83000));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ZhNumPower(pub Number);
+impl Display for ZhNumPower {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_padded(f, |w| fmt_zh_num_power(self.0, w))
+    }
+}
+
+/// [`to_zh_range`] write to [`Write`] impl
+///
+/// [`Write`]: fmt::Write
+pub fn fmt_zh_range(
+    range: &RangeInclusive<Number>,
+    mut f: impl fmt::Write,
+) -> fmt::Result {
+    fmt_zh_num(*range.start(), &mut f)?;
+    if range.start() != range.end() {
+        f.write_str("到")?;
+        fmt_zh_num(*range.end(), &mut f)?;
+    }
+    Ok(())
+}
+
+/// Convert range to zh numbers joined by `到`
+///
+/// # Examples
+/// ```
+/// # use zh_num::to_zh_range;
+/// assert_eq!(to_zh_range(&(3..=5)), "三到五");
+/// assert_eq!(to_zh_range(&(30000..=50000)), "三万到五万");
+/// assert_eq!(to_zh_range(&(3..=3)), "三");
+/// ```
+pub fn to_zh_range(range: &RangeInclusive<Number>) -> String {
+    let mut s = String::new();
+    fmt_zh_range(range, &mut s).unwrap();
+    s
+}
+
+/// [`fmt_zh_range`] wrapper, impl [`Display`]
+///
+/// # Examples
+/// ```
+/// # use zh_num::{ZhRange, to_zh_range};
+/// assert_eq!(ZhRange(10..=20).to_string(), to_zh_range(&(10..=20)));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ZhRange(pub RangeInclusive<Number>);
+impl Display for ZhRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_padded(f, |w| fmt_zh_range(&self.0, w))
+    }
+}
+
+/// [`to_zh_num_suzhou`] write to [`Write`] impl
+///
+/// [`Write`]: fmt::Write
+pub fn fmt_zh_num_suzhou(num: Number, f: impl fmt::Write) -> fmt::Result {
+    SuzhouNum::fmt_suzhou(num, f)
+}
+
+/// Convert number to Suzhou numerals, trailing zeros are replaced by
+/// the magnitude of the first digit
+///
+/// # Examples
+/// ```
+/// # use zh_num::to_zh_num_suzhou;
+/// assert_eq!(to_zh_num_suzhou(4502), "〤〥〇〢千");
+/// assert_eq!(to_zh_num_suzhou(4500), "〤〥千");
+/// assert_eq!(to_zh_num_suzhou(1123), "〡一〢三千");
+/// assert_eq!(to_zh_num_suzhou(7), "〧");
+/// ```
+pub fn to_zh_num_suzhou(num: Number) -> String {
+    ZhNumSuzhou(num).to_string()
+}
+
+/// [`fmt_zh_num_suzhou`] wrapper, impl [`Display`]
+///
+/// # Examples
+/// ```
+/// # use zh_num::{ZhNumSuzhou, to_zh_num_suzhou};
+/// assert_eq!(ZhNumSuzhou(83362).to_string(), to_zh_num_suzhou(83362));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ZhNumSuzhou(pub Number);
+impl Display for ZhNumSuzhou {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_padded(f, |w| fmt_zh_num_suzhou(self.0, w))
+    }
+}
+
+/// [`to_zh_num_rod`] write to [`Write`] impl
+///
+/// [`Write`]: fmt::Write
+pub fn fmt_zh_num_rod(num: Number, f: impl fmt::Write) -> fmt::Result {
+    RodNum::fmt_rod(num, f)
+}
+
+/// Convert number to counting rod numerals
+///
+/// Units, hundreds, ... use the vertical forms,
+/// tens, thousands, ... use the horizontal forms, zero is `〇`
+///
+/// # Examples
+/// ```
+/// # use zh_num::to_zh_num_rod;
+/// assert_eq!(to_zh_num_rod(7), "\u{1D366}");
+/// assert_eq!(to_zh_num_rod(6708), "\u{1D36E}\u{1D366}〇\u{1D367}");
+/// ```
+pub fn to_zh_num_rod(num: Number) -> String {
+    ZhNumRod(num).to_string()
+}
+
+/// [`fmt_zh_num_rod`] wrapper, impl [`Display`]
+///
+/// # Examples
+/// ```
+/// # use zh_num::{ZhNumRod, to_zh_num_rod};
+/// assert_eq!(ZhNumRod(83362).to_string(), to_zh_num_rod(83362));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ZhNumRod(pub Number);
+impl Display for ZhNumRod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_padded(f, |w| fmt_zh_num_rod(self.0, w))
+    }
+}
+
+/// Style of [`fmt_zh_digits`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct DigitsStyle {
+    /// Use upper digits, e.g `壹`
+    pub upper: bool,
+    /// Use `〇` instead of `零`
+    pub circle_zero: bool,
+    /// Use `幺` instead of `一`, ignored when [`upper`](Self::upper)
+    pub yao: bool,
+}
+impl DigitsStyle {
+    fn digit(&self, digit: Number) -> char {
+        match digit {
+            0 if self.circle_zero => '〇',
+            1 if self.yao && !self.upper => '幺',
+            _ if self.upper => UpperNum::one(digit),
+            _ => LowerNum::one(digit),
+        }
+    }
+}
+
+/// [`to_zh_digits`] write to [`Write`] impl
+///
+/// [`Write`]: fmt::Write
+pub fn fmt_zh_digits(
+    num: Number,
+    style: DigitsStyle,
+    mut f: impl fmt::Write,
+) -> fmt::Result {
+    for pow_d in (0..=num.checked_ilog10().unwrap_or(0)).rev() {
+        let digit = num / Number::pow(10, pow_d) % 10;
+        f.write_char(style.digit(digit))?;
+    }
+    Ok(())
+}
+
+/// Convert number to zh digits one by one, inverse of [`hard_number`]
+///
+/// # Examples
+/// ```
+/// # use zh_num::{to_zh_digits, DigitsStyle};
+/// let style = DigitsStyle::default();
+/// assert_eq!(to_zh_digits(10086, style), "一零零八六");
+///
+/// let style = DigitsStyle { circle_zero: true, ..Default::default() };
+/// assert_eq!(to_zh_digits(2024, style), "二〇二四");
+///
+/// let style = DigitsStyle { yao: true, ..Default::default() };
+/// assert_eq!(to_zh_digits(110, style), "幺幺零");
+///
+/// let style = DigitsStyle { upper: true, ..Default::default() };
+/// assert_eq!(to_zh_digits(10086, style), "壹零零捌陆");
+/// ```
+///
+/// [`hard_number`]: parser::hard_number
+pub fn to_zh_digits(num: Number, style: DigitsStyle) -> String {
+    ZhDigits(num, style).to_string()
+}
+
+/// [`fmt_zh_digits`] wrapper, impl [`Display`]
+///
+/// # Examples
+/// ```
+/// # use zh_num::{ZhDigits, DigitsStyle, to_zh_digits};
+/// let style = DigitsStyle::default();
+/// assert_eq!(ZhDigits(83362, style).to_string(), to_zh_digits(83362, style));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ZhDigits(pub Number, pub DigitsStyle);
+impl Display for ZhDigits {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        fmt_zh_num_upper(self.0, f)
+        let mut style = self.1;
+        style.upper |= f.alternate();
+        fmt_padded(f, |w| fmt_zh_digits(self.0, style, w))
+    }
+}
+
+/// Style of [`fmt_zh_code`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CodeStyle<'a> {
+    /// Style of each digit, `幺` is used by default
+    pub digits: DigitsStyle,
+    /// Use `两` instead of `二`, ignored when upper
+    pub liang: bool,
+    /// Group sizes from left, the rest digits are the last group
+    pub groups: &'a [usize],
+    /// Written between groups
+    pub separator: char,
+}
+impl CodeStyle<'_> {
+    /// Mobile phone numbers, e.g `幺三八 幺二三四 五六七八`
+    pub const MOBILE: CodeStyle<'static> = CodeStyle {
+        groups: &[3, 4, 4],
+        ..CodeStyle::DEFAULT
+    };
+    /// Landline numbers without area code, e.g `六二三四 五六七八`
+    pub const LANDLINE: CodeStyle<'static> = CodeStyle {
+        groups: &[4, 4],
+        ..CodeStyle::DEFAULT
+    };
+    const DEFAULT: CodeStyle<'static> = CodeStyle {
+        digits: DigitsStyle { upper: false, circle_zero: false, yao: true },
+        liang: false,
+        groups: &[],
+        separator: ' ',
+    };
+}
+impl Default for CodeStyle<'_> {
+    fn default() -> Self {
+        CodeStyle::DEFAULT
+    }
+}
+
+/// [`to_zh_code`] write to [`Write`] impl
+///
+/// Spaces and dashes in `code` are ignored,
+/// other non-digit chars are written as is and not counted in the groups
+///
+/// [`Write`]: fmt::Write
+pub fn fmt_zh_code(
+    code: &str,
+    style: CodeStyle<'_>,
+    mut f: impl fmt::Write,
+) -> fmt::Result {
+    let mut groups = style.groups.iter();
+    let mut group = groups.next();
+    let mut count = 0;
+    for ch in code.chars() {
+        let Some(digit) = digit_value(ch) else {
+            if !matches!(ch, ' ' | '\u{3000}' | '-' | '—') {
+                f.write_char(ch)?;
+            }
+            continue;
+        };
+        if group.is_some_and(|&n| count == n) {
+            f.write_char(style.separator)?;
+            group = groups.next();
+            count = 0;
+        }
+        let ch = match digit {
+            2 if style.liang && !style.digits.upper => '两',
+            _ => style.digits.digit(digit.into()),
+        };
+        f.write_char(ch)?;
+        count += 1;
+    }
+    Ok(())
+}
+
+/// Convert phone numbers or codes to the spoken zh digits
+///
+/// # Examples
+/// ```
+/// # use zh_num::{to_zh_code, CodeStyle};
+/// assert_eq!(to_zh_code("13812345678", CodeStyle::MOBILE), "幺三八 幺二三四 五六七八");
+/// assert_eq!(to_zh_code("6234-5678", CodeStyle::LANDLINE), "六二三四 五六七八");
+///
+/// let style = CodeStyle { liang: true, groups: &[2, 2], ..Default::default() };
+/// assert_eq!(to_zh_code("1202", style), "幺两 零两");
+/// assert_eq!(to_zh_code("CA1521", CodeStyle::default()), "CA幺五二幺");
+/// ```
+pub fn to_zh_code(code: &str, style: CodeStyle<'_>) -> String {
+    let mut s = String::new();
+    fmt_zh_code(code, style, &mut s).unwrap();
+    s
+}
+
+/// Full-width digits wrapper, impl [`Display`]
+///
+/// # Examples
+/// ```
+/// # use zh_num::FullWidthNum;
+/// assert_eq!(FullWidthNum(1234).to_string(), "１２３４");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FullWidthNum(pub Number);
+impl Display for FullWidthNum {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_padded(f, |w| {
+            self.0.to_string()
+                .chars()
+                .filter_map(|ch| ch.to_digit(10))
+                .try_for_each(|d| {
+                    w.write_char(char::from_u32('０' as u32 + d).unwrap())
+                })
+        })
+    }
+}
+
+/// Grouping of [`GroupedNum`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum Grouping {
+    /// Every three digits, e.g `1,234,567`
+    #[default]
+    Thousands,
+    /// Every four digits, like `万` and `亿`, e.g `123,4567`
+    Wan,
+}
+impl Grouping {
+    /// Digits count of a group
+    pub fn size(self) -> usize {
+        match self {
+            Grouping::Thousands => 3,
+            Grouping::Wan => 4,
+        }
+    }
+}
+
+/// ASCII digits grouped by `,` wrapper, impl [`Display`]
+///
+/// # Examples
+/// ```
+/// # use zh_num::{GroupedNum, Grouping};
+/// assert_eq!(GroupedNum(1234567, Grouping::Thousands).to_string(), "1,234,567");
+/// assert_eq!(GroupedNum(12345678, Grouping::Wan).to_string(), "1234,5678");
+/// assert_eq!(GroupedNum(123, Grouping::Thousands).to_string(), "123");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GroupedNum(pub Number, pub Grouping);
+impl Display for GroupedNum {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = self.0.to_string();
+        let size = self.1.size();
+        let head = (s.len() - 1) % size + 1;
+        fmt_padded(f, |w| {
+            w.write_str(&s[..head])?;
+            s.as_bytes()[head..]
+                .chunks(size)
+                .try_for_each(|group| {
+                    w.write_str(",")?;
+                    w.write_str(std::str::from_utf8(group).unwrap())
+                })
+        })
     }
 }
 
@@ -356,12 +1659,184 @@ mod tests {
             ("陆仟零柒", 6007),
             ("叁佰陆拾壹万贰仟贰佰柒拾柒", 3612277),
             ("伍万零壹拾贰", 50012),
+            ("１２３４", 1234),
+            ("１２３４章", 1234),
+            ("１2３4", 1234),
         ];
         for (src, num) in datas {
             assert_eq!(parser::number(src).map(|x| x.0), Ok(num), "{src} -> {num}");
         }
     }
 
+    #[test]
+    fn test_grouped() {
+        let datas = [
+            ("1,234", 1234, ""),
+            ("1,234,567章", 1234567, "章"),
+            ("1234,5678", 12345678, ""),
+            ("1,2345,6789", 123456789, ""),
+            ("1,23", 1, ",23"),
+            ("1,", 1, ","),
+            ("12345,678", 12345, ",678"),
+            ("1,234,5678", 1234, ",5678"),
+            ("1,234,", 1234, ","),
+            ("１,２３４", 1234, ""),
+        ];
+        for (src, num, rest) in datas {
+            assert_eq!(parser::digits(src), Ok((num, rest)), "{src}");
+        }
+        // only `digits` accepts the groups
+        let datas = [
+            ("1,234", 1, ",234"),
+            ("1234,5678", 1234, ",5678"),
+            ("2023,2024年", 2023, ",2024年"),
+            ("１,２３４", 1, ",２３４"),
+        ];
+        for (src, num, rest) in datas {
+            assert_eq!(parser::number(src), Ok((num, rest)), "{src}");
+            assert_eq!(parser::plain_digits(src), Ok((num, rest)), "{src}");
+        }
+        for num in [0, 1, 999, 1000, 9999, 10000, 1234567, Number::MAX] {
+            for grouping in [Grouping::Thousands, Grouping::Wan] {
+                let s = GroupedNum(num, grouping).to_string();
+                assert_eq!(parser::digits(&s), Ok((num, "")), "{s}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_power() {
+        let datas = [
+            ("十的一次方", 10),
+            ("十的零次方", 1),
+            ("十的八次方", 1_0000_0000),
+            ("十的十九次方", 10_000_000_000_000_000_000),
+            ("二的六十三次幂", 1 << 63),
+            ("一千乘十的三次方", 100_0000),
+            ("3*10^5", 30_0000),
+            ("2×十的八次方", 2_0000_0000),
+            ("十八乘以10的十八次方", 18_000_000_000_000_000_000),
+        ];
+        for (src, num) in datas {
+            assert_eq!(parser::power_number(src), Ok((num, "")), "{src}");
+        }
+        for src in ["十的二十次方", "二的六十四次幂", "十九乘十的十八次方", "十的", "十乘"] {
+            assert!(parser::power_number(src).is_err(), "{src}");
+        }
+        let datas = [
+            (0, "零"),
+            (1, "一"),
+            (10, "十"),
+            (20, "二十"),
+            (120, "一百二十"),
+            (3000, "三千"),
+            (1_0000, "十的四次方"),
+            (12_0000, "十二乘十的四次方"),
+            (3_0000_0000, "三乘十的八次方"),
+        ];
+        for (num, s) in datas {
+            assert_eq!(to_zh_num_power(num), s, "{num}");
+        }
+        for num in [0, 1, 10, 30, 1_0000_0000, 3_0000_0000, 12_3000, Number::MAX] {
+            let s = to_zh_num_power(num);
+            let parsed = parser::power_number(&s)
+                .or_else(|_| parser::number(&s));
+            assert_eq!(parsed, Ok((num, "")), "{s}");
+        }
+    }
+
+    #[test]
+    fn test_range() {
+        let datas = [
+            ("三到五天", 3..=5, "天"),
+            ("十至二十", 10..=20, ""),
+            ("3~5", 3..=5, ""),
+            ("3-5", 3..=5, ""),
+            ("三—五", 3..=5, ""),
+            ("三千到五千", 3000..=5000, ""),
+            ("三到五万元", 3_0000..=5_0000, "元"),
+            ("三到五十万", 30_0000..=50_0000, ""),
+            ("三到十", 3..=10, ""),
+            ("三到五十", 3..=50, ""),
+            ("三到五百二十", 3..=520, ""),
+            ("十五到二十万", 15_0000..=20_0000, ""),
+            ("三百到五百万元", 300_0000..=500_0000, "元"),
+            ("十五到二十亿", 15_0000_0000..=20_0000_0000, ""),
+            ("十五万到二十万", 15_0000..=20_0000, ""),
+            ("十五到二十万零一", 15..=20_0001, ""),
+            ("三五个", 3..=5, "个"),
+            ("一两个", 1..=2, "个"),
+            ("七八十", 70..=80, ""),
+            ("两三千", 2000..=3000, ""),
+            ("三四万", 3_0000..=4_0000, ""),
+            ("十五六", 15..=16, ""),
+            ("一百二三十", 120..=130, ""),
+            ("一万五六千", 1_5000..=1_6000, ""),
+            ("二十三四万", 23_0000..=24_0000, ""),
+        ];
+        for (src, range, rest) in datas {
+            assert_eq!(parser::range_number(src), Ok((range, rest)), "{src}");
+        }
+        for src in ["五到三", "三到三", "三六", "一百二三", "三", "到五"] {
+            assert!(parser::range_number(src).is_err(), "{src}");
+        }
+    }
+
+    #[test]
+    fn test_int() {
+        fn check<T>(nums: impl IntoIterator<Item = T>)
+        where T: ZhInt + fmt::Debug + PartialEq,
+        {
+            for num in nums {
+                let s = to_zh_int(num);
+                assert_eq!(parser::integer::<T>(&s), Ok((num, "")), "{s}");
+                let s = to_zh_int_upper(num);
+                assert_eq!(parser::integer::<T>(&s), Ok((num, "")), "{s}");
+            }
+        }
+        check(u8::MIN..=u8::MAX);
+        check(i8::MIN..=i8::MAX);
+        check([0, 1, u16::MAX]);
+        check([i32::MIN, -1, 0, i32::MAX]);
+        check([usize::MAX, isize::MAX as usize]);
+        check([i64::MIN, i64::MAX]);
+        check([isize::MIN, isize::MAX]);
+        check([0, Number::MAX as u128 + 1, u128::MAX]);
+        check([i128::MIN, -1, i128::MAX]);
+
+        assert_eq!(to_zh_int(-10086i32), "负一万零八十六");
+        assert_eq!(to_zh_int(u64::MAX), to_zh_num(u64::MAX));
+        assert_eq!(to_zh_int(10u128.pow(16)), "一亿亿");
+        assert_eq!(parser::integer::<i8>("负零"), Ok((0, "")));
+        assert!(parser::integer::<u16>("六万五千五百三十六").is_err());
+        assert!(parser::integer::<i16>("负三万二千七百六十九").is_err());
+        assert!(parser::integer::<u128>("一亿亿亿亿亿").is_err());
+        assert!(parser::number("一亿亿亿").is_err());
+        assert!(parser::hard_number("九二三四五六七八九零一二三四五六七八九零").is_err());
+    }
+
+    #[test]
+    fn test_fmt_padded() {
+        let datas = [
+            (format!("{:6}|", ZhNum(12)), "  十二|"),
+            (format!("{:<6}|", ZhNum(12)), "十二  |"),
+            (format!("{:^6}|", ZhNum(12)), " 十二 |"),
+            (format!("{:*>5}|", ZhNum(12)), "*十二|"),
+            (format!("{:2}|", ZhNum(10086)), "一万零八十六|"),
+            (format!("{:#8}|", ZhNum(12)), "  壹拾贰|"),
+            (format!("{:8}|", ZhNumUpper(12)), "  壹拾贰|"),
+            (format!("{:#}", ZhDigits(120, DigitsStyle::default())), "壹贰零"),
+            (format!("{:6}|", FullWidthNum(12)), "  １２|"),
+            (format!("{:<6}|", GroupedNum(1234, Grouping::Thousands)), "1,234 |"),
+            (format!("{:8}|", ZhNumSuzhou(12)), "  〡二十|"),
+            (format!("{:>8}|", ZhRange(3..=5)), "  三到五|"),
+            (format!("{:6}|", ZhNumPower(1_0000)), "十的四次方|"),
+        ];
+        for (s, expected) in datas {
+            assert_eq!(s, expected);
+        }
+    }
+
     #[test]
     fn test_to_zh() {
         let datas = [
@@ -444,6 +1919,150 @@ mod tests {
             });
     }
 
+    #[test]
+    fn test_fmt_options() {
+        let liang = FmtOptions { liang: true, ..Default::default() };
+        let lead = FmtOptions { leading_one: true, ..Default::default() };
+        let circle = FmtOptions { circle_zero: true, ..Default::default() };
+        let datas = [
+            (2, liang, "二"),
+            (12, liang, "十二"),
+            (20, liang, "二十"),
+            (200, liang, "两百"),
+            (2222, liang, "两千两百二十二"),
+            (2_0000, liang, "两万"),
+            (12_0000, liang, "十二万"),
+            (2_0002, liang, "两万零二"),
+            (2_0000_0000, liang, "两亿"),
+            (2_0000_0000_0000, liang, "两万亿"),
+            (1_0002_0000, liang, "一亿零两万"),
+            (10, lead, "一十"),
+            (15_0000, lead, "一十五万"),
+            (110, lead, "一百一十"),
+            (0, circle, "〇"),
+            (1_0000_0001, circle, "一亿〇一"),
+        ];
+        for (num, opts, src) in datas {
+            assert_eq!(to_zh_num_with(num, opts), src, "{num} -> {src}");
+            assert_eq!(parser::number(src), Ok((num, "")), "{src} -> {num}");
+        }
+        let all = FmtOptions { leading_one: true, liang: true, circle_zero: true };
+        (0..150)
+            .chain((150..10000000).step_by(293))
+            .chain((10000000..1000000000).step_by(38004))
+            .for_each(|n| {
+                let s = to_zh_num_with(n, all);
+                assert_eq!(parser::number(&s), Ok((n, "")), "{s}");
+                let s = to_zh_num_upper_with(n, all);
+                assert!(!s.contains('两'), "{s}");
+                assert_eq!(parser::number(&s), Ok((n, "")), "{s}");
+            });
+    }
+
+    #[test]
+    fn test_suzhou() {
+        let datas = [
+            (0, "〇"),
+            (1, "〡"),
+            (10, "〡十"),
+            (11, "〡一十"),
+            (12, "〡二十"),
+            (21, "〢一十"),
+            (111, "〡一〡百"),
+            (1023, "〡〇〢三千"),
+            (1_0000_0000, "〡亿"),
+            (12_0000_0000_0000, "〡二十万亿"),
+        ];
+        for (num, src) in datas {
+            assert_eq!(to_zh_num_suzhou(num), src, "{num} -> {src}");
+        }
+        (0..150)
+            .chain((150..10000000).step_by(293))
+            .chain((10000000..1000000000).step_by(38004))
+            .chain([Number::MAX])
+            .for_each(|n| {
+                let s = to_zh_num_suzhou(n);
+                assert_eq!(parser::suzhou_number(&s), Ok((n, "")), "{s}");
+            });
+    }
+
+    #[test]
+    fn test_rod() {
+        let datas = [
+            (0, "〇"),
+            (1, "\u{1D360}"),
+            (10, "\u{1D369}〇"),
+            (11, "\u{1D369}\u{1D360}"),
+            (105, "\u{1D360}〇\u{1D364}"),
+            (2222, "\u{1D36A}\u{1D361}\u{1D36A}\u{1D361}"),
+        ];
+        for (num, src) in datas {
+            assert_eq!(to_zh_num_rod(num), src, "{num} -> {src}");
+            assert_eq!(parser::rod_number(src), Ok((num, "")), "{src} -> {num}");
+        }
+        (0..150)
+            .chain((150..10000000).step_by(293))
+            .chain([Number::MAX])
+            .for_each(|n| {
+                let s = to_zh_num_rod(n);
+                assert_eq!(parser::rod_number(&s), Ok((n, "")), "{s}");
+            });
+    }
+
+    #[test]
+    fn test_digits() {
+        let styles = [false, true].into_iter()
+            .flat_map(|a| [(a, false), (a, true)])
+            .flat_map(|(a, b)| [(a, b, false), (a, b, true)])
+            .map(|(upper, circle_zero, yao)| DigitsStyle {
+                upper,
+                circle_zero,
+                yao,
+            });
+        for style in styles {
+            (0..150)
+                .chain((150..10000000).step_by(293))
+                .chain([Number::MAX])
+                .for_each(|n| {
+                    let s = to_zh_digits(n, style);
+                    assert_eq!(parser::hard_number(&s), Ok((n, "")), "{s}");
+                });
+        }
+    }
+
+    #[test]
+    fn test_code() {
+        let datas = [
+            ("", CodeStyle::MOBILE, ""),
+            ("138", CodeStyle::MOBILE, "幺三八"),
+            ("1381", CodeStyle::MOBILE, "幺三八 幺"),
+            ("138 1234 5678", CodeStyle::MOBILE, "幺三八 幺二三四 五六七八"),
+            ("138123456789", CodeStyle::MOBILE, "幺三八 幺二三四 五六七八 九"),
+            ("010-62345678", CodeStyle {
+                groups: &[3, 8],
+                separator: '-',
+                ..Default::default()
+            }, "零幺零-六二三四五六七八"),
+            ("１２０", CodeStyle {
+                liang: true,
+                digits: DigitsStyle { upper: true, ..Default::default() },
+                ..Default::default()
+            }, "壹贰零"),
+            ("CA1521", CodeStyle::default(), "CA幺五二幺"),
+            ("MU5101", CodeStyle { groups: &[2], ..Default::default() }, "MU五幺 零幺"),
+        ];
+        for (src, style, code) in datas {
+            assert_eq!(to_zh_code(src, style), code, "{src} -> {code}");
+            let digits = src.chars()
+                .filter_map(digit_value)
+                .map(|d| char::from_digit(d, 10).unwrap())
+                .collect::<String>();
+            if !digits.is_empty() && src.chars().all(|ch| !ch.is_ascii_alphabetic()) {
+                assert_eq!(parser::code_digits(code), Ok((digits, "")));
+            }
+        }
+    }
+
     #[test]
     #[ignore = "long-time-test"]
     fn test_num_range() {
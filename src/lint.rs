@@ -0,0 +1,313 @@
+use std::{
+    fmt::{self, Display},
+    ops::Range,
+};
+
+use crate::{
+    is_numeral, parse_number, readings, to_zh_num, to_zh_num_upper,
+    Number, Parsed, ReadingKind, Style,
+};
+
+/// Rule of a [`Lint`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Rule {
+    /// `一` before the leading `十`, e.g `一十零一`
+    LeadingOne,
+    /// Redundant `零`, e.g `十零一` `一百零零一`
+    ExtraZero,
+    /// Missing `零` between the units, e.g `一万一百`
+    MissingZero,
+    /// Missing `一` before `十` after other units, e.g `一百十` `一万零十`
+    MissingOne,
+    /// `两` not before `百` `千` `万` `亿`, e.g `两十` `两佰`
+    Liang,
+    /// Lower and upper chars in one number, e.g `壹千二百`
+    MixedCase,
+    /// `〇` in cardinal numbers, e.g `一百〇一`
+    CircleZero,
+    /// Variant chars, e.g `弌` `陌`
+    Variant,
+    /// Digits mixed with units, e.g `百零零` `一零零十三`
+    DigitSequence,
+    /// More than one plausible reading, e.g `一万一`, see [`readings`]
+    Ambiguous,
+    /// Other non-standard spellings
+    NonCanonical,
+}
+impl Rule {
+    /// Id of the rule, e.g `extra-zero`
+    pub fn id(self) -> &'static str {
+        match self {
+            Rule::LeadingOne => "leading-one",
+            Rule::ExtraZero => "extra-zero",
+            Rule::MissingZero => "missing-zero",
+            Rule::MissingOne => "missing-one",
+            Rule::Liang => "liang",
+            Rule::MixedCase => "mixed-case",
+            Rule::CircleZero => "circle-zero",
+            Rule::Variant => "variant",
+            Rule::DigitSequence => "digit-sequence",
+            Rule::Ambiguous => "ambiguous",
+            Rule::NonCanonical => "non-canonical",
+        }
+    }
+}
+impl Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(self.id())
+    }
+}
+
+/// A non-standard zh number found by [`lint`]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Lint<'a> {
+    pub rule: Rule,
+    /// Byte range of [`text`](Self::text) in the input
+    pub span: Range<usize>,
+    pub text: &'a str,
+    /// Result of [`parser::number`], or [`parser::hard_number`]
+    /// if [`DigitSequence`](Rule::DigitSequence)
+    ///
+    /// [`parser::number`]: crate::parser::number
+    /// [`parser::hard_number`]: crate::parser::hard_number
+    pub number: Number,
+    /// The canonical spelling, upper if the text has any upper char,
+    /// [`None`] if [`Ambiguous`](Rule::Ambiguous)
+    pub fix: Option<String>,
+}
+
+fn is_unit(ch: char) -> bool {
+    matches!(ch,
+        '十' | '拾' | '百' | '佰' | '陌' | '千' | '仟' | '阡' | '万' | '亿')
+}
+
+/// Map to the lower chars, and remove the leading `一` of `一十`
+fn normalize(text: &str) -> String {
+    let s = text.chars()
+        .map(|ch| match ch {
+            '〇' => '零',
+            '壹' | '弌' | '幺' => '一',
+            '贰' | '弍' | '两' => '二',
+            '叁' | '弎' => '三',
+            '肆' => '四',
+            '伍' => '五',
+            '陆' => '六',
+            '柒' => '七',
+            '捌' => '八',
+            '玖' => '九',
+            '拾' => '十',
+            '佰' | '陌' => '百',
+            '仟' | '阡' => '千',
+            _ => ch,
+        })
+        .collect::<String>();
+    match s.strip_prefix("一十") {
+        Some(rest) => format!("十{rest}"),
+        None => s,
+    }
+}
+
+/// Canonical spelling, upper if `text` has any upper char
+fn fix(text: &str, num: Number) -> String {
+    if text.chars().any(|ch| Style::of_char(ch) == Some(Style::Upper)) {
+        to_zh_num_upper(num)
+    } else {
+        to_zh_num(num)
+    }
+}
+
+fn rules(parsed: &Parsed<'_>) -> Vec<Rule> {
+    let text = parsed.text;
+    let chars = text.chars().collect::<Vec<_>>();
+    let mut rules = vec![];
+    if text.starts_with("一十") {
+        rules.push(Rule::LeadingOne);
+    }
+    let norm = normalize(text);
+    let canonical = to_zh_num(parsed.number);
+    if norm != canonical {
+        let count = |s: &str, ch| s.matches(ch).count();
+        let (zeros, ones) = (count(&norm, '零'), count(&norm, '一'));
+        rules.push(if zeros > count(&canonical, '零') {
+            Rule::ExtraZero
+        } else if zeros < count(&canonical, '零') {
+            Rule::MissingZero
+        } else if ones < count(&canonical, '一') {
+            Rule::MissingOne
+        } else {
+            Rule::NonCanonical
+        });
+    }
+    let liang = chars.iter().enumerate()
+        .filter(|&(_, &ch)| ch == '两')
+        .any(|(i, _)| {
+            !matches!(chars.get(i + 1), Some('百' | '千' | '万' | '亿'))
+        });
+    if liang {
+        rules.push(Rule::Liang);
+    }
+    if parsed.style == Style::Mixed {
+        rules.push(Rule::MixedCase);
+    }
+    if text.contains('〇') {
+        rules.push(Rule::CircleZero);
+    }
+    if text.contains(['弌', '弍', '弎', '幺', '陌', '阡']) {
+        rules.push(Rule::Variant);
+    }
+    rules
+}
+
+/// Scan zh numbers in `s`, returns lints ordered by position,
+/// a number may have more than one lint with the same span
+///
+/// Numbers without any unit are ignored, e.g `两个` `二〇二四`,
+/// so are ASCII and full-width digits
+///
+/// # Examples
+/// ```
+/// # use zh_num::{lint, Rule};
+/// let lints = lint("共一十零一人, 两佰元");
+/// let lints = lints.iter()
+///     .map(|l| (l.rule, l.text, l.fix.as_deref()))
+///     .collect::<Vec<_>>();
+/// assert_eq!(lints, [
+///     (Rule::LeadingOne, "一十零一", Some("十一")),
+///     (Rule::ExtraZero, "一十零一", Some("十一")),
+///     (Rule::Liang, "两佰", Some("贰佰")),
+///     (Rule::MixedCase, "两佰", Some("贰佰")),
+/// ]);
+/// assert!(lint("二〇二四年一月, 两千零一个").is_empty());
+/// ```
+pub fn lint(s: &str) -> Vec<Lint<'_>> {
+    let mut lints = vec![];
+    let mut i = 0;
+    while let Some(ch) = s[i..].chars().next() {
+        let rest = &s[i..];
+        let run = rest.find(|ch| !is_numeral(ch)).unwrap_or(rest.len());
+        if run == 0 || ch.is_ascii_digit() || ('０'..='９').contains(&ch) {
+            i += ch.len_utf8();
+            continue;
+        }
+        if !rest[..run].contains(is_unit) {
+            i += run;
+            continue;
+        }
+
+        let rs = readings(rest);
+        let digit_seq = rs.iter()
+            .find(|r| r.kind == ReadingKind::DigitSequence);
+        if digit_seq.is_some() || rs.len() > 1 {
+            let r = digit_seq.unwrap_or(&rs[0]);
+            let len = rest.len() - rs.iter().map(|r| r.rest.len()).min().unwrap();
+            let text = &rest[..len];
+            let (rule, fix) = match digit_seq {
+                Some(r) => (Rule::DigitSequence, Some(fix(text, r.number))),
+                None => (Rule::Ambiguous, None),
+            };
+            lints.push(Lint { rule, span: i..i + len, text, number: r.number, fix });
+            i += len;
+            continue;
+        }
+        let Ok(parsed) = parse_number(rest) else {
+            i += ch.len_utf8();
+            continue;
+        };
+        let fix = fix(parsed.text, parsed.number);
+        let span = i..i + parsed.text.len();
+        for rule in rules(&parsed) {
+            lints.push(Lint {
+                rule,
+                span: span.clone(),
+                text: parsed.text,
+                number: parsed.number,
+                fix: Some(fix.clone()),
+            });
+        }
+        i = span.end;
+    }
+    lints
+}
+
+/// Replace the numbers found by [`lint`] with the fixes
+///
+/// # Examples
+/// ```
+/// # use zh_num::fix_lints;
+/// assert_eq!(fix_lints("共一十零一人, 两佰元"), "共十一人, 贰佰元");
+/// assert_eq!(fix_lints("一万一"), "一万一");
+/// ```
+pub fn fix_lints(s: &str) -> String {
+    let mut result = String::with_capacity(s.len());
+    let mut end = 0;
+    for lint in lint(s) {
+        let Some(fix) = lint.fix else { continue };
+        if lint.span.start < end {
+            continue;
+        }
+        result.push_str(&s[end..lint.span.start]);
+        result.push_str(&fix);
+        end = lint.span.end;
+    }
+    result.push_str(&s[end..]);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Rule::*;
+
+    #[test]
+    fn test_lint() {
+        let datas: [(&str, &[Rule], Option<&str>); 18] = [
+            ("一十零一", &[LeadingOne, ExtraZero], Some("十一")),
+            ("一十二", &[LeadingOne], Some("十二")),
+            ("百零零", &[DigitSequence], Some("一百")),
+            ("一百零零一", &[ExtraZero], Some("一百零一")),
+            ("一万一百", &[MissingZero], Some("一万零一百")),
+            ("一万零十", &[MissingOne], Some("一万零一十")),
+            ("一百十", &[MissingOne], Some("一百一十")),
+            ("两佰", &[Liang, MixedCase], Some("贰佰")),
+            ("两十", &[Liang], Some("二十")),
+            ("十两", &[Liang], Some("十二")),
+            ("壹千二百", &[MixedCase], Some("壹仟贰佰")),
+            ("一百〇一", &[CircleZero], Some("一百零一")),
+            ("弌百", &[Variant], Some("一百")),
+            ("壹陌", &[Variant], Some("壹佰")),
+            ("一万一", &[Ambiguous], None),
+            ("三百五", &[Ambiguous], None),
+            ("一零零十三", &[DigitSequence], Some("一万零一十三")),
+            ("十二万零八", &[], None),
+        ];
+        for (src, rules, fix) in datas {
+            let s = format!("第{src}章");
+            let lints = lint(&s);
+            assert_eq!(lints.iter().map(|l| l.rule).collect::<Vec<_>>(), rules, "{src}");
+            for l in lints {
+                assert_eq!(l.text, src, "{src}");
+                assert_eq!(&s[l.span], src, "{src}");
+                assert_eq!(l.fix.as_deref(), fix, "{src}");
+            }
+        }
+        let ignored = [
+            "", "章", "两个", "二〇二四", "1234", "１２３４", "十一", "两千零一",
+            "壹万零捌拾陆", "一百二十三万", "十亿零一",
+        ];
+        for src in ignored {
+            assert_eq!(lint(src), [], "{src}");
+        }
+    }
+
+    #[test]
+    fn test_fix_lints() {
+        let datas = [
+            ("第一十二章第两佰节", "第十二章第贰佰节"),
+            ("一十, 1234, 一百〇一", "十, 1234, 一百零一"),
+            ("三百五十, 三百五", "三百五十, 三百五"),
+        ];
+        for (src, fixed) in datas {
+            assert_eq!(fix_lints(src), fixed, "{src}");
+        }
+    }
+}
@@ -1,22 +1,18 @@
-use std::io::{self, stderr, stdin, stdout, Write, BufRead};
-use clap::{Parser, Arg, ArgAction};
+use std::io::{self, stderr, stdin, stdout, BufRead, Write};
+use clap::{Parser, Subcommand, Arg, ArgAction};
+use encoding_rs::{Encoding, UTF_8};
 
 use zh_num::{
-    parser::{hard_number, number},
-    Number, ZhNum, ZhNumUpper,
+    fast::{try_hard_number, try_number},
+    parser::{ast_number, digits, plain_digits},
+    encoding::{Decoder, Encoder, EncodingError},
+    stream::{convert_lines, convert_text},
+    digit_value, display_width, fix_lints, is_numeral, fmt_zh_num_upper_with, fmt_zh_num_with, lint,
+    to_zh_num, upper_number,
+    DigitsStyle, FmtOptions, FullWidthNum, GroupedNum, Grouping, Number,
+    ParseError, ZhDigits,
 };
 
-const CRLF: &str = "\r\n";
-const LF: &str = "\n";
-
-fn get_eol(s: &str) -> &str {
-    s.ends_with(CRLF)
-        .then_some(CRLF)
-        .or_else(|| s.ends_with(LF)
-            .then_some(LF))
-        .unwrap_or_default()
-}
-
 #[derive(Debug, Default, Parser)]
 #[command(help_template = "\
 {usage-heading} {usage}
@@ -43,32 +39,181 @@ struct Config {
     dump: bool,
     #[arg(short = 'D', help = "类似 -d, 但是中文数字是大写")]
     is_upper: bool,
+    #[arg(short = 'e', help = "类似 -d, 但是逐位转换, 如 `一零零八六`")]
+    digits: bool,
+    #[arg(short = 'o', help = "使用 `〇` 代替 `零`")]
+    circle_zero: bool,
+    #[arg(short = 'l', help = "在 `百` `千` 和单独的 `万` `亿` 前使用 `两`, 如 `两万`")]
+    liang: bool,
+    #[arg(short = 'i', help = "保留开头的 `十` 前的 `一`, 如 `一十二`")]
+    leading_one: bool,
+    #[arg(short = 'y', help = "逐位转换时使用 `幺` 代替 `一`")]
+    yao: bool,
     #[arg(short, help = "转换时保留结果之外的文本")]
     rem: bool,
     #[arg(short = 'a', help = "转换硬数字, 如 `千零二三` `一零零十三`")]
     hard: bool,
+    #[arg(short = 'w', help = "输出全角数字, 如 `１２３４`")]
+    full_width: bool,
+    #[arg(short = 'g', help = "输出千分位分组的数字, 如 `1,234,567`")]
+    thousands: bool,
+    #[arg(short = 'G', help = "输出万分位分组的数字, 如 `123,4567`")]
+    wan: bool,
+    #[arg(short = 'c', help = "识别以 `,` 分组的ASCII数字, 如 `1,234,567` `1234,5678`")]
+    grouped: bool,
+    #[arg(short = 'p', help = "将转换后的数字填充到指定的显示宽度, 中文数字的宽度为2")]
+    pad: Option<usize>,
+    #[arg(short = 'L', help = "填充时左对齐, 默认右对齐")]
+    left: bool,
+    #[arg(long, help = "严格识别财务大写数字, 只允许 `零壹贰叁肆伍陆柒捌玖拾佰仟万亿`, 并且必须有应有的 `零`")]
+    strict: bool,
+    #[arg(long, help = "显示中文数字的分解, 如 `一万零八十六 = 1×10000 + 8×10 + 6`, ASCII数字也会分解")]
+    explain: bool,
+    #[arg(long, global = true, value_parser = parse_encoding, help = "输入的编码, 如 `gbk` `gb18030` `big5`, 默认为 `utf-8`")]
+    input_encoding: Option<&'static Encoding>,
+    #[arg(long, global = true, value_parser = parse_encoding, help = "输出的编码, 如 `gbk` `gb18030` `big5`, 默认为 `utf-8`")]
+    output_encoding: Option<&'static Encoding>,
     #[arg(short, help = "识别时跳过一部分字符, 如果给定了-r则会留在结果中")]
     #[arg(default_value_t = 0)]
     skip_ch: usize,
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    #[command(about = "检查不规范的中文数字, 如 `一十零一` `两佰`, 发现时退出码为1")]
+    Lint {
+        #[arg(long, help = "输出修正后的文本, 而不是检查结果")]
+        fix: bool,
+    },
 }
 impl Config {
-    fn num_fmt(&self) -> fn(&mut io::StdoutLock, Number) -> io::Result<()> {
-        if !self.is_upper {
-            |f, n| write!(f, "{}", ZhNum(n))
+    fn write_padded(&self, f: &mut impl Write, s: &str) -> io::Result<()> {
+        let Some(width) = self.pad else { return write!(f, "{s}") };
+        let pad = " ".repeat(width.saturating_sub(display_width(s)));
+        if self.left {
+            write!(f, "{s}{pad}")
+        } else {
+            write!(f, "{pad}{s}")
+        }
+    }
+    fn write_num(&self, f: &mut impl Write, n: Number) -> io::Result<()> {
+        if self.digits {
+            let style = DigitsStyle {
+                upper: self.is_upper,
+                circle_zero: self.circle_zero,
+                yao: self.yao,
+            };
+            return self.write_padded(f, &ZhDigits(n, style).to_string());
+        }
+        let mut opts = if self.is_upper {
+            FmtOptions::UPPER
+        } else {
+            FmtOptions::default()
+        };
+        opts.circle_zero |= self.circle_zero;
+        opts.liang |= self.liang;
+        opts.leading_one |= self.leading_one;
+        let mut s = String::new();
+        if self.is_upper {
+            fmt_zh_num_upper_with(n, opts, &mut s).unwrap();
+        } else {
+            fmt_zh_num_with(n, opts, &mut s).unwrap();
+        }
+        self.write_padded(f, &s)
+    }
+    fn grouping(&self) -> Option<Grouping> {
+        if self.thousands {
+            Some(Grouping::Thousands)
+        } else if self.wan {
+            Some(Grouping::Wan)
         } else {
-            |f, n| write!(f, "{}", ZhNumUpper(n))
+            None
         }
     }
     fn init_dependenices(mut self) -> Self {
         if self.dump && self.hard { eprintln!("警告: 在指定 -d 时 -a 被忽略"); }
-        self.dump |= self.is_upper;
+        if self.dump && self.explain { eprintln!("警告: 在指定 -d 时 --explain 被忽略"); }
+        if self.dump && self.strict { eprintln!("警告: 在指定 -d 时 --strict 被忽略"); }
+        if !self.dump && self.strict && (self.hard || self.explain) {
+            eprintln!("警告: 在指定 --strict 时 -a --explain 被忽略");
+        }
+        if !self.dump && self.strict && self.grouped {
+            eprintln!("警告: 在指定 --strict 时 -c 被忽略");
+        }
+        if !self.dump && !self.strict && self.explain && self.hard {
+            eprintln!("警告: 在指定 --explain 时 -a 被忽略");
+        }
+        if !self.dump && !self.strict && self.explain && (self.full_width || self.thousands || self.wan) {
+            eprintln!("警告: 在指定 --explain 时 -w -g -G 被忽略");
+        }
+        if !self.digits && self.yao {
+            eprintln!("警告: 在未指定 -e 时 -y 被忽略");
+        }
+        if self.digits && (self.liang || self.leading_one) {
+            eprintln!("警告: 在指定 -e 时 -l -i 被忽略");
+        }
+        self.dump |= self.is_upper || self.digits;
+        if self.dump && self.full_width { eprintln!("警告: 在指定 -d 时 -w 被忽略"); }
+        if self.dump && (self.thousands || self.wan) {
+            eprintln!("警告: 在指定 -d 时 -g -G 被忽略");
+        }
+        if self.full_width && (self.thousands || self.wan) {
+            eprintln!("警告: 在指定 -g -G 时 -w 被忽略");
+        }
+        if self.thousands && self.wan { eprintln!("警告: 在指定 -g 时 -G 被忽略"); }
+        if self.pad.is_none() && self.left { eprintln!("警告: 在未指定 -p 时 -L 被忽略"); }
         self
     }
 }
 
+fn parse_encoding(label: &str) -> Result<&'static Encoding, String> {
+    Encoding::for_label(label.as_bytes())
+        .ok_or_else(|| format!("未知的编码 `{label}`"))
+}
+
+fn warn_encoding(enc: &'static Encoding, e: EncodingError) {
+    let name = enc.name();
+    match e {
+        EncodingError::Malformed { line, bytes } => {
+            eprintln!("警告: 第 {line} 行无法以 {name} 转换的字节 {bytes:02X?}");
+        },
+        EncodingError::Unmappable { line, ch } => {
+            eprintln!("警告: 第 {line} 行无法以 {name} 编码的字符 {ch:?}");
+        },
+    }
+}
+
+/// Column and message of a parse error
+fn error_message(e: ParseError) -> (usize, String) {
+    (e.location.column, format!("expected {}", e.expected))
+}
+
+/// Write the lints of each line, returns whether any lint is found
+fn lint_lines(input: impl BufRead, mut output: impl Write) -> io::Result<bool> {
+    let mut found = false;
+    convert_text(input, io::sink(), is_numeral, |lnum, column, text, _| {
+        for l in lint(text) {
+            found = true;
+            let col = column + text[..l.span.start].chars().count() + 1;
+            write!(output, "{lnum}:{col} {} `{}`", l.rule, l.text)?;
+            match l.fix {
+                Some(fix) => writeln!(output, " -> `{fix}`")?,
+                None => writeln!(output)?,
+            }
+        }
+        Ok(())
+    })?;
+    output.flush()?;
+    Ok(found)
+}
+
 fn main() -> io::Result<()> {
     let cfg = Config::parse().init_dependenices();
-    let Config { rem, skip_ch, dump, hard, .. } = cfg;
+    let Config { rem, skip_ch, dump, hard, full_width, explain, strict, grouped, .. } = cfg;
+    let grouping = cfg.grouping();
+    let ascii_digits = if grouped { digits } else { plain_digits };
     macro_rules! skip_ch_line {
         ($line:expr) => {{
             fn convf<'a, T, F>(f: F) -> F
@@ -88,87 +233,102 @@ fn main() -> io::Result<()> {
             })($line)
         }};
     }
+    let input: Box<dyn BufRead> = match cfg.input_encoding {
+        Some(enc) if enc != UTF_8 => {
+            Box::new(Decoder::new(stdin().lock(), enc, move |e| warn_encoding(enc, e)))
+        },
+        _ => Box::new(stdin().lock()),
+    };
+    // the encoder is finished when dropped by `convert_lines`
+    let output: Box<dyn Write> = match cfg.output_encoding {
+        Some(enc) if enc.output_encoding() != UTF_8 => {
+            Box::new(Encoder::new(stdout().lock(), enc, move |e| warn_encoding(enc, e)))
+        },
+        _ => Box::new(stdout().lock()),
+    };
+    match cfg.command {
+        Some(Command::Lint { fix: false }) => {
+            if lint_lines(input, output)? {
+                std::process::exit(1);
+            }
+            return Ok(());
+        },
+        Some(Command::Lint { fix: true }) => {
+            return convert_text(input, output, is_numeral, |_, _, text, out| {
+                write!(out, "{}", fix_lints(text))
+            });
+        },
+        None => (),
+    }
     match dump {
-        false => {
-            let mut line = String::new();
-            let mut stdin = stdin().lock();
-            let mut lnum = 0u64;
-            loop {
-                line.clear();
-                if 0 == stdin.read_line(&mut line)? { break Ok(()) }
-                lnum += 1;
-
-                let (prefix, line) = skip_ch_line!(&line);
-                let result = if !hard {
-                    number(line)
+        false => convert_lines(input, output, rem, |lnum, head, out| {
+            let (prefix, line) = skip_ch_line!(head);
+            let result = if explain && !strict {
+                ast_number(line).map(|(ast, s)| {
+                    let text = &line[..line.len()-s.len()];
+                    (format!("{text} = {ast}"), s)
+                }).or_else(|e| {
+                    // ASCII digits are explained by its zh num
+                    let (n, s) = ascii_digits(line).map_err(|_| e)?;
+                    let (ast, _) = ast_number(&to_zh_num(n))
+                        .expect("zh num should be parsed");
+                    let text = &line[..line.len()-s.len()];
+                    Ok((format!("{text} = {ast}"), s))
+                }).map_err(error_message)
+            } else {
+                let result = if strict {
+                    upper_number(line).map_err(|e| (e.column, e.kind.to_string()))
+                } else if grouped && line.starts_with(|ch| digit_value(ch).is_some()) {
+                    digits(line).map_err(error_message)
                 } else {
-                    hard_number(line)
+                    let result = if !hard { try_number(line) } else { try_hard_number(line) };
+                    result.map_err(error_message)
                 };
-                let (n, rem_str) = result
-                    .map(|(n, s)| (Some(n), s))
-                    .or_else(|e| {
-                        writeln!(stderr(), "`{}` {lnum}:{} expected {}",
-                            line.trim_end(),
-                            e.location.column+skip_ch,
-                            e.expected,
-                        )?;
-                        io::Result::Ok((None, line))
-                    })?;
-                let mut stdout = stdout().lock();
-                if rem {
-                    write!(stdout, "{prefix}")?;
-                }
-                if let Some(n) = n {
-                    write!(stdout, "{n}")?;
-                }
-                if rem {
-                    write!(stdout, "{rem_str}")?;
-                } else {
-                    write!(stdout, "{}", get_eol(rem_str))?;
-                }
+                result.map(|(n, s)| {
+                    let num = match grouping {
+                        Some(g) => GroupedNum(n, g).to_string(),
+                        None if full_width => FullWidthNum(n).to_string(),
+                        None => n.to_string(),
+                    };
+                    (num, s)
+                })
+            };
+            let (num, rem_str) = result
+                .map(|(num, s)| (Some(num), s))
+                .or_else(|(column, msg)| {
+                    writeln!(stderr(), "`{}` {lnum}:{} {msg}",
+                        line.trim_end(),
+                        column+skip_ch,
+                    )?;
+                    io::Result::Ok((None, line))
+                })?;
+            if rem {
+                write!(out, "{prefix}")?;
             }
-        },
-        true => {
-            let mut line = String::new();
-            let mut stdin = stdin().lock();
-            let mut lnum = 0u64;
-            loop {
-                line.clear();
-                if 0 == stdin.read_line(&mut line)? { break Ok(()) }
-                lnum += 1;
-
-                let (prefix, line) = skip_ch_line!(&line);
-                let rem_idx = line
-                    .find(|ch| !char::is_ascii_digit(&ch))
-                    .unwrap_or(line.len());
-                let (part, rem_str) = line.split_at(rem_idx);
-                let num = part
-                    .parse()
-                    .map(Some)
-                    .or_else(|e| {
-                        writeln!(
-                            stderr(),
-                            "`{part}` ({}) {lnum}: {e}",
-                            rem_str.trim_end(),
-                        )?;
-                        io::Result::Ok(None)
-                    })?;
-
-                let mut stdout = stdout().lock();
-                if rem {
-                    write!(stdout, "{prefix}")?;
-                }
-                if let Some(num) = num {
-                    cfg.num_fmt()(&mut stdout, num)?;
-                } else {
-                    write!(stdout, "{part}")?;
-                }
-                if rem {
-                    write!(stdout, "{rem_str}")?;
-                } else {
-                    write!(stdout, "{}", get_eol(rem_str))?;
-                }
+            if let Some(num) = num {
+                cfg.write_padded(out, &num)?;
             }
-        },
+            Ok(head.len() - rem_str.len())
+        }),
+        true => convert_lines(input, output, rem, |lnum, head, out| {
+            let (prefix, line) = skip_ch_line!(head);
+            let (num, rem_str) = ascii_digits(line)
+                .map(|(n, s)| (Some(n), s))
+                .or_else(|e| {
+                    writeln!(stderr(), "`{}` {lnum}:{} expected {}",
+                        line.trim_end(),
+                        e.location.column+skip_ch,
+                        e.expected,
+                    )?;
+                    io::Result::Ok((None, line))
+                })?;
+            if rem {
+                write!(out, "{prefix}")?;
+            }
+            if let Some(num) = num {
+                cfg.write_num(out, num)?;
+            }
+            Ok(head.len() - rem_str.len())
+        }),
     }
 }
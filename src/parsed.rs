@@ -0,0 +1,309 @@
+use std::ops::Range;
+
+use crate::{
+    digit_value, fast, is_numeral, parser, to_zh_num, to_zh_num_upper,
+    FullWidthNum, Number, ParseError,
+};
+
+/// Style of the parsed number text
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Style {
+    /// ASCII digits, e.g `1234`
+    Ascii,
+    /// Full-width digits, e.g `１２３４`
+    FullWidth,
+    /// Lower zh numbers, e.g `一千二百三十四`
+    Lower,
+    /// Upper zh numbers, e.g `壹仟贰佰叁拾肆`
+    Upper,
+    /// More than one style, e.g `壹千二百` `１2３4`
+    Mixed,
+}
+impl Style {
+    /// Style of a single char,
+    /// [`None`] if it is used by both lower and upper or not a numeral
+    pub(crate) fn of_char(ch: char) -> Option<Self> {
+        Some(match ch {
+            '0'..='9' => Style::Ascii,
+            '０'..='９' => Style::FullWidth,
+            '零' | '万' | '亿' => return None,
+            '壹' | '贰' | '叁' | '肆' | '伍' | '陆' | '柒' | '捌' | '玖'
+            | '拾' | '佰' | '仟' | '陌' | '阡' => Style::Upper,
+            _ if is_numeral(ch) => Style::Lower,
+            _ => return None,
+        })
+    }
+
+    /// Style of the number text
+    fn of_str(s: &str) -> Self {
+        s.chars()
+            .filter_map(Self::of_char)
+            .reduce(|a, b| if a == b { a } else { Style::Mixed })
+            .unwrap_or(Style::Lower)
+    }
+}
+
+/// Parsed number with metadata, see [`parse_number`]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Parsed<'a> {
+    pub number: Number,
+    /// Consumed text of the number
+    pub text: &'a str,
+    /// Rest text after the number
+    pub rest: &'a str,
+    /// Byte range of [`text`](Self::text) in the input
+    pub span: Range<usize>,
+    /// Chars count of [`text`](Self::text)
+    pub chars: usize,
+    pub style: Style,
+    /// [`text`](Self::text) is the canonical spelling of the style,
+    /// e.g the result of [`to_zh_num`] or [`to_zh_num_upper`],
+    /// a text contains separators is not canonical
+    pub canonical: bool,
+}
+impl<'a> Parsed<'a> {
+    /// Build from the input, consumed byte range and number
+    pub(crate) fn new(s: &'a str, span: Range<usize>, number: Number) -> Self {
+        let text = &s[span.clone()];
+        let style = Style::of_str(text);
+        let canonical = match style {
+            Style::Ascii => *text == number.to_string(),
+            Style::FullWidth => *text == FullWidthNum(number).to_string(),
+            Style::Lower => *text == to_zh_num(number),
+            Style::Upper => *text == to_zh_num_upper(number),
+            Style::Mixed => false,
+        };
+        Self {
+            number,
+            text,
+            rest: &s[span.end..],
+            chars: text.chars().count(),
+            span,
+            style,
+            canonical,
+        }
+    }
+}
+
+/// Like [`parser::number`], but returns a [`Parsed`] with metadata
+///
+/// # Examples
+/// ```
+/// # use zh_num::{parse_number, Style};
+/// let parsed = parse_number("一万零八十六章").unwrap();
+/// assert_eq!(parsed.number, 10086);
+/// assert_eq!(parsed.text, "一万零八十六");
+/// assert_eq!(parsed.rest, "章");
+/// assert_eq!(parsed.span, 0..18);
+/// assert_eq!(parsed.chars, 6);
+/// assert_eq!(parsed.style, Style::Lower);
+/// assert!(parsed.canonical);
+///
+/// let parsed = parse_number("壹千二百").unwrap();
+/// assert_eq!(parsed.style, Style::Mixed);
+/// assert!(!parsed.canonical);
+///
+/// let parsed = parse_number("一万零十").unwrap();
+/// assert_eq!(parsed.style, Style::Lower);
+/// assert!(!parsed.canonical);
+/// ```
+///
+/// [`parser::number`]: crate::parser::number
+pub fn parse_number(s: &str) -> Result<Parsed<'_>, ParseError> {
+    let (number, rest) = fast::try_number(s)?;
+    Ok(Parsed::new(s, 0..s.len() - rest.len(), number))
+}
+
+/// Options of [`parse_number_with`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct ParseOptions<'a> {
+    /// Chars allowed between the chars of a number
+    pub separators: &'a [char],
+    /// Accept ASCII digits grouped by `,`, see [`parser::digits`]
+    ///
+    /// [`parser::digits`]: crate::parser::digits
+    pub grouping: bool,
+}
+impl ParseOptions<'_> {
+    /// Spaces, full-width spaces, commas, `、`, zero-width chars and line breaks
+    pub const SEPARATORS: &'static [char] = &[
+        ' ', '\t', '\u{3000}', ',', '，', '、',
+        '\u{200B}', '\u{200C}', '\u{200D}', '\u{2060}', '\u{FEFF}',
+        '\r', '\n',
+    ];
+
+    /// Allow [`SEPARATORS`](Self::SEPARATORS)
+    pub const LENIENT: ParseOptions<'static> = ParseOptions {
+        separators: Self::SEPARATORS,
+        grouping: false,
+    };
+}
+
+/// Like [`parse_number`], but with options
+///
+/// The separators are only skipped between the zh numeral chars of a number,
+/// and [`Parsed::span`] covers them.
+/// ASCII and full-width digits are never joined across separators,
+/// e.g `1, 2` is `1`
+///
+/// # Examples
+/// ```
+/// # use zh_num::{parse_number_with, ParseOptions};
+/// let opts = ParseOptions::LENIENT;
+/// let parsed = parse_number_with("一万 二千　三百 章", opts).unwrap();
+/// assert_eq!(parsed.number, 12300);
+/// assert_eq!(parsed.text, "一万 二千　三百");
+/// assert_eq!(parsed.rest, " 章");
+///
+/// let parsed = parse_number_with("一亿\n三千万", opts).unwrap();
+/// assert_eq!(parsed.number, 1_3000_0000);
+///
+/// let opts = ParseOptions { separators: &['-'], ..Default::default() };
+/// let parsed = parse_number_with("一万-二千 三百", opts).unwrap();
+/// assert_eq!(parsed.number, 12000);
+/// assert_eq!(parsed.rest, " 三百");
+///
+/// let parsed = parse_number_with("1, 2, 3", ParseOptions::LENIENT).unwrap();
+/// assert_eq!(parsed.number, 1);
+/// assert_eq!(parsed.rest, ", 2, 3");
+///
+/// let opts = ParseOptions { grouping: true, ..Default::default() };
+/// let parsed = parse_number_with("1,234,567元", opts).unwrap();
+/// assert_eq!(parsed.number, 1234567);
+/// assert_eq!(parsed.text, "1,234,567");
+/// ```
+pub fn parse_number_with<'a>(
+    s: &'a str,
+    opts: ParseOptions<'_>,
+) -> Result<Parsed<'a>, ParseError> {
+    if opts.grouping && s.starts_with(|ch| digit_value(ch).is_some()) {
+        let (number, rest) = parser::digits(s)?;
+        return Ok(Parsed::new(s, 0..s.len() - rest.len(), number));
+    }
+    let is_zh = |ch| is_numeral(ch) && digit_value(ch).is_none();
+    if opts.separators.is_empty() || !s.starts_with(is_zh) {
+        return parse_number(s);
+    }
+    // zh numeral chars of the number without separators,
+    // and the end index in `s` of each char
+    let mut norm = String::new();
+    let mut ends = vec![];
+    let mut chars = s.char_indices().peekable();
+    while let Some((i, ch)) = chars.next() {
+        if !is_zh(ch) { break }
+        norm.push(ch);
+        ends.push((norm.len(), i + ch.len_utf8()));
+
+        let mut rest = chars.clone();
+        while rest.next_if(|&(_, ch)| opts.separators.contains(&ch)).is_some() {}
+        if rest.peek().is_some_and(|&(_, ch)| is_zh(ch)) {
+            chars = rest;
+        }
+    }
+    let (number, rest) = fast::try_number(&norm)
+        .map_err(|mut e| {
+            let offset = ends.iter()
+                .take_while(|&&(end, _)| end <= e.location.offset)
+                .last()
+                .map_or(0, |&(_, end)| end);
+            e.location = peg::Parse::position_repr(s, offset);
+            e
+        })?;
+    let consumed = norm.len() - rest.len();
+    let end = ends.iter()
+        .find(|&&(end, _)| end == consumed)
+        .map_or(0, |&(_, end)| end);
+    Ok(Parsed::new(s, 0..end, number))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_number() {
+        let datas = [
+            ("1234", 4, Style::Ascii, true),
+            ("01234", 5, Style::Ascii, false),
+            ("１２３４", 4, Style::FullWidth, true),
+            ("１2３4", 4, Style::Mixed, false),
+            ("零", 1, Style::Lower, true),
+            ("〇", 1, Style::Lower, false),
+            ("十一", 2, Style::Lower, true),
+            ("一十一", 3, Style::Lower, false),
+            ("两千", 2, Style::Lower, false),
+            ("一万零一", 4, Style::Lower, true),
+            ("一万一", 3, Style::Lower, false),
+            ("壹拾壹", 3, Style::Upper, true),
+            ("壹万零捌拾陆", 6, Style::Upper, true),
+            ("壹万捌拾陆", 5, Style::Upper, false),
+            ("壹千二百", 4, Style::Mixed, false),
+            ("陆仟零柒", 4, Style::Upper, true),
+        ];
+        for (src, chars, style, canonical) in datas {
+            let s = format!("{src}章");
+            let parsed = parse_number(&s).unwrap();
+            assert_eq!(parsed.text, src);
+            assert_eq!(parsed.rest, "章");
+            assert_eq!(parsed.span, 0..src.len());
+            assert_eq!(parsed.chars, chars, "{src}");
+            assert_eq!(parsed.style, style, "{src}");
+            assert_eq!(parsed.canonical, canonical, "{src}");
+        }
+    }
+
+    #[test]
+    fn test_parse_number_with() {
+        let opts = ParseOptions::LENIENT;
+        let datas = [
+            ("一万 二千 三百", 12300, "一万 二千 三百", ""),
+            ("一万 二千 三百 章", 12300, "一万 二千 三百", " 章"),
+            ("一万\u{200B}二千", 12000, "一万\u{200B}二千", ""),
+            ("一亿\r\n三千万", 1_3000_0000, "一亿\r\n三千万", ""),
+            ("一, 二", 1, "一", ", 二"),
+            ("1, 2", 1, "1", ", 2"),
+            ("1、2、3", 1, "1", "、2、3"),
+            ("3, 5个", 3, "3", ", 5个"),
+            ("1 2", 1, "1", " 2"),
+            ("十 1", 10, "十", " 1"),
+            ("1,234,567元", 1, "1", ",234,567元"),
+            ("三 章", 3, "三", " 章"),
+        ];
+        for (src, number, text, rest) in datas {
+            let parsed = parse_number_with(src, opts).unwrap();
+            assert_eq!(parsed.number, number, "{src}");
+            assert_eq!(parsed.text, text, "{src}");
+            assert_eq!(parsed.rest, rest, "{src}");
+            assert_eq!(parsed.span, 0..text.len(), "{src}");
+        }
+        assert!(parse_number_with(" 一", opts).is_err());
+
+        // the error location is in the input
+        let datas = [
+            ("一亿 亿亿", "一亿 亿亿".len(), 1, 6),
+            ("一亿\n三千万 亿亿", "一亿\n三千万 亿亿".len(), 2, 7),
+            ("万 一", 0, 1, 1),
+        ];
+        for (src, offset, line, column) in datas {
+            let err = parse_number_with(src, opts).unwrap_err();
+            assert_eq!(err.location.offset, offset, "{src}");
+            assert_eq!((err.location.line, err.location.column), (line, column), "{src}");
+        }
+        let parsed = parse_number_with("一万 二千", ParseOptions::default());
+        assert_eq!(parsed.unwrap().rest, " 二千");
+
+        let opts = ParseOptions { grouping: true, ..ParseOptions::LENIENT };
+        let datas = [
+            ("1,234,567元", 1234567, "1,234,567", "元"),
+            ("1234,5678", 12345678, "1234,5678", ""),
+            ("2023, 2024", 2023, "2023", ", 2024"),
+            ("一万 二千", 12000, "一万 二千", ""),
+        ];
+        for (src, number, text, rest) in datas {
+            let parsed = parse_number_with(src, opts).unwrap();
+            assert_eq!(parsed.number, number, "{src}");
+            assert_eq!(parsed.text, text, "{src}");
+            assert_eq!(parsed.rest, rest, "{src}");
+        }
+    }
+}
@@ -0,0 +1,139 @@
+use crate::{fast, parser, Number};
+
+/// Reason of a [`Reading`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ReadingKind {
+    /// Result of [`parser::number`], e.g `一万一` is `10001`
+    Formal,
+    /// The trailing digit is of the next lower unit, e.g `一万一` is `11000`
+    Colloquial,
+    /// Result of [`parser::hard_number`], e.g `一零零十三` is `10013`
+    DigitSequence,
+}
+
+/// A plausible reading of zh numbers, see [`readings`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Reading<'a> {
+    pub number: Number,
+    pub kind: ReadingKind,
+    /// Rest text after the number
+    pub rest: &'a str,
+}
+
+/// Digit value of a char, includes `零`
+fn digit(ch: char) -> Option<Number> {
+    parser::one_num(ch.encode_utf8(&mut [0; 4]), 0).ok()
+}
+
+/// Value of the units which the lower unit is not `十`
+fn big_unit(ch: char) -> Option<Number> {
+    Some(match ch {
+        '百' | '佰' | '陌' => 100,
+        '千' | '仟' | '阡' => 1000,
+        '万' => 1_0000,
+        '亿' => 1_0000_0000,
+        _ => return None,
+    })
+}
+
+fn colloquial(text: &str, formal: Number) -> Option<Number> {
+    let mut chars = text.chars().rev();
+    let last = digit(chars.next()?).filter(|&d| d != 0)?;
+    let unit = big_unit(chars.next()?)?;
+    formal.checked_sub(last)?
+        .checked_add(last * (unit / 10))
+}
+
+/// Returns all plausible readings of the zh numbers at the start of `s`,
+/// different readings have different numbers,
+/// and the [`Formal`] reading is always the first if any
+///
+/// Only the readings consuming the longest text are returned,
+/// e.g `二〇二四` is not read as `2` followed by `〇二四`
+///
+/// # Examples
+/// ```
+/// # use zh_num::{readings, ReadingKind::*};
+/// let rs = readings("一万一")
+///     .into_iter()
+///     .map(|r| (r.number, r.kind))
+///     .collect::<Vec<_>>();
+/// assert_eq!(rs, [(10001, Formal), (11000, Colloquial)]);
+///
+/// let rs = readings("一零零十三")
+///     .into_iter()
+///     .map(|r| (r.number, r.kind, r.rest))
+///     .collect::<Vec<_>>();
+/// assert_eq!(rs, [(10013, DigitSequence, "")]);
+///
+/// assert_eq!(readings("一万零一").len(), 1);
+/// assert!(readings("章").is_empty());
+/// ```
+///
+/// [`Formal`]: ReadingKind::Formal
+pub fn readings(s: &str) -> Vec<Reading<'_>> {
+    let mut result: Vec<Reading<'_>> = vec![];
+    let mut formal_rest = s;
+    let mut push = |number, kind, rest| {
+        if result.iter().all(|r: &Reading<'_>| r.number != number) {
+            result.push(Reading { number, kind, rest });
+        }
+    };
+
+    if let Some((number, rest)) = fast::number(s) {
+        formal_rest = rest;
+        push(number, ReadingKind::Formal, rest);
+        let text = &s[..s.len() - rest.len()];
+        if let Some(number) = colloquial(text, number) {
+            push(number, ReadingKind::Colloquial, rest);
+        }
+    }
+    // only when the digits are more than the formal reading consumed
+    if let Some((number, rest)) = fast::hard_number(s)
+        .filter(|(_, rest)| rest.len() < formal_rest.len())
+    {
+        let text = &s[..s.len() - rest.len()];
+        let chars = text.chars().collect::<Vec<_>>();
+        let adjacent_digits = chars.windows(2)
+            .any(|w| digit(w[0]).is_some() && digit(w[1]).is_some());
+        if adjacent_digits {
+            push(number, ReadingKind::DigitSequence, rest);
+        }
+    }
+    if let Some(min) = result.iter().map(|r| r.rest.len()).min() {
+        result.retain(|r| r.rest.len() == min);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ReadingKind::*;
+
+    #[test]
+    fn test_readings() {
+        let datas: [(&str, &[(Number, ReadingKind)]); 13] = [
+            ("一", &[(1, Formal)]),
+            ("十一", &[(11, Formal)]),
+            ("一万一", &[(10001, Formal), (11000, Colloquial)]),
+            ("两千一", &[(2001, Formal), (2100, Colloquial)]),
+            ("三百五", &[(305, Formal), (350, Colloquial)]),
+            ("壹仟伍", &[(1005, Formal), (1500, Colloquial)]),
+            ("一亿一", &[(1_0000_0001, Formal), (1_1000_0000, Colloquial)]),
+            ("一万一千一", &[(11001, Formal), (11100, Colloquial)]),
+            ("一万零一", &[(10001, Formal)]),
+            ("二〇二四", &[(2024, DigitSequence)]),
+            ("一零零十三", &[(10013, DigitSequence)]),
+            ("百零零", &[(100, DigitSequence)]),
+            ("1234", &[(1234, Formal)]),
+        ];
+        for (src, expected) in datas {
+            let rs = readings(src)
+                .into_iter()
+                .map(|r| (r.number, r.kind))
+                .collect::<Vec<_>>();
+            assert_eq!(rs, expected, "{src}");
+        }
+    }
+}
@@ -0,0 +1,379 @@
+//! Line based streaming conversion over [`BufRead`] and [`Write`]
+//!
+//! Only the head of each line is buffered, the rest of the line is copied
+//! or skipped chunk by chunk, so long lines and invalid UTF-8 are passed
+//! through without being held in memory.
+//! [`convert_text`] passes the whole line in segments instead of the head
+
+use std::{
+    io::{self, BufRead, Read, Write},
+    str,
+};
+
+/// Max bytes of the line head passed to the converter of [`convert_lines`]
+pub const HEAD_LEN: usize = 64 * 1024;
+
+/// Copy or skip the rest of the line, return the line ending
+fn finish_line<R, W>(
+    input: &mut R,
+    mut output: Option<&mut W>,
+    mut last: Option<u8>,
+) -> io::Result<&'static [u8]>
+where R: BufRead,
+      W: Write,
+{
+    loop {
+        let buf = input.fill_buf()?;
+        if buf.is_empty() {
+            return Ok(b"");
+        }
+        let (len, found) = match buf.iter().position(|&b| b == b'\n') {
+            Some(i) => (i + 1, true),
+            None => (buf.len(), false),
+        };
+        if let Some(output) = &mut output {
+            output.write_all(&buf[..len])?;
+        }
+        let prev = if len >= 2 { Some(buf[len - 2]) } else { last };
+        last = Some(buf[len - 1]);
+        input.consume(len);
+        if found {
+            return Ok(if prev == Some(b'\r') { b"\r\n" } else { b"\n" });
+        }
+    }
+}
+
+/// Line ending of a complete line
+fn eol(line: &[u8]) -> &'static [u8] {
+    if line.ends_with(b"\r\n") {
+        b"\r\n"
+    } else if line.ends_with(b"\n") {
+        b"\n"
+    } else {
+        b""
+    }
+}
+
+/// Convert each line from `input` to `output`
+///
+/// `convert` is called with the line number, the line head and `output`,
+/// it writes the converted text and returns the consumed bytes of the head.
+/// The line head is the valid UTF-8 prefix of at most [`HEAD_LEN`] bytes,
+/// includes the line ending if any.
+///
+/// The rest of the line is copied as is when `keep_rest`,
+/// otherwise only the line ending is written
+///
+/// # Errors
+/// The errors of `input`, `output` and `convert`,
+/// or [`InvalidInput`] if `convert` consumed more than the head
+///
+/// [`InvalidInput`]: io::ErrorKind::InvalidInput
+///
+/// # Examples
+/// ```
+/// # use std::io::Write;
+/// # use zh_num::{parser::number, stream::convert_lines};
+/// let input = "一万零八十六章\n十二\r\n第三\n";
+/// let mut output = vec![];
+/// convert_lines(input.as_bytes(), &mut output, true, |_, head, out| {
+///     let Ok((num, rest)) = number(head) else { return Ok(0) };
+///     write!(out, "{num}")?;
+///     Ok(head.len() - rest.len())
+/// }).unwrap();
+/// assert_eq!(output, "10086章\n12\r\n第三\n".as_bytes());
+/// ```
+pub fn convert_lines<R, W, F>(
+    mut input: R,
+    mut output: W,
+    keep_rest: bool,
+    mut convert: F,
+) -> io::Result<()>
+where R: BufRead,
+      W: Write,
+      F: FnMut(u64, &str, &mut W) -> io::Result<usize>,
+{
+    let mut head = Vec::new();
+    let mut lnum = 0;
+    loop {
+        head.clear();
+        (&mut input).take(HEAD_LEN as u64).read_until(b'\n', &mut head)?;
+        if head.is_empty() {
+            break output.flush();
+        }
+        lnum += 1;
+        let complete = head.ends_with(b"\n") || head.len() < HEAD_LEN;
+
+        let valid = match str::from_utf8(&head) {
+            Ok(s) => s.len(),
+            Err(e) => e.valid_up_to(),
+        };
+        let text = str::from_utf8(&head[..valid]).unwrap();
+        let consumed = convert(lnum, text, &mut output)?;
+        if consumed > valid {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("consumed {consumed} of {valid} bytes of the line head"),
+            ));
+        }
+
+        let last = head.last().copied();
+        if keep_rest {
+            output.write_all(&head[consumed..])?;
+            if !complete {
+                finish_line(&mut input, Some(&mut output), last)?;
+            }
+        } else if complete {
+            output.write_all(eol(&head))?;
+        } else {
+            let eol = finish_line(&mut input, None::<&mut W>, last)?;
+            output.write_all(eol)?;
+        }
+    }
+}
+
+/// Convert the whole text of each line from `input` to `output`
+///
+/// Unlike [`convert_lines`], every valid UTF-8 run of the line is passed
+/// to `convert` in segments, read chunk by chunk.
+/// A segment does not end inside a run of chars matching `join`,
+/// unless the run is longer than [`HEAD_LEN`] bytes.
+/// Invalid bytes and the line endings are copied as is
+///
+/// `convert` is called with the line number, the column of the segment,
+/// the segment and `output`, it writes the converted segment.
+/// The column is the chars count before the segment in the line,
+/// each invalid byte is counted as a char
+///
+/// # Errors
+/// The errors of `input`, `output` and `convert`
+///
+/// # Examples
+/// ```
+/// # use std::io::Write;
+/// # use zh_num::{is_numeral, stream::convert_text};
+/// let input = b"ab\xff\xe5\x8d\x81\xe4\xba\x8c\n";
+/// let mut output = vec![];
+/// convert_text(&input[..], &mut output, is_numeral, |lnum, column, text, out| {
+///     write!(out, "[{lnum}:{column} {text}]")
+/// }).unwrap();
+/// assert_eq!(output, [&b"[1:0 ab]\xff"[..], "[1:3 十二]\n".as_bytes()].concat());
+/// ```
+pub fn convert_text<R, W, J, F>(
+    mut input: R,
+    mut output: W,
+    join: J,
+    mut convert: F,
+) -> io::Result<()>
+where R: BufRead,
+      W: Write,
+      J: Fn(char) -> bool,
+      F: FnMut(u64, usize, &str, &mut W) -> io::Result<()>,
+{
+    let mut buf = Vec::new();
+    let mut lnum = 0;
+    let mut column = 0;
+    let mut new_line = true;
+    loop {
+        let chunk = input.fill_buf()?;
+        let (len, eol) = match chunk.iter().position(|&b| b == b'\n') {
+            Some(i) => (i + 1, true),
+            None => (chunk.len(), false),
+        };
+        let end = eol || chunk.is_empty();
+        if len != 0 && new_line {
+            lnum += 1;
+            column = 0;
+            new_line = false;
+        }
+        buf.extend_from_slice(&chunk[..len]);
+        input.consume(len);
+
+        let mut start = 0;
+        while start < buf.len() {
+            let rest = &buf[start..];
+            let (valid, invalid) = match str::from_utf8(rest) {
+                Ok(_) => (rest.len(), 0),
+                Err(e) => match e.error_len() {
+                    Some(n) => (e.valid_up_to(), n),
+                    None if end => (e.valid_up_to(), rest.len() - e.valid_up_to()),
+                    // the char may be completed by the next chunk
+                    None => (e.valid_up_to(), 0),
+                },
+            };
+            let mut text = str::from_utf8(&rest[..valid]).unwrap();
+            let open = !end && invalid == 0;
+            if open {
+                // the line ending or the run of `join` chars
+                // may continue in the next chunk
+                let cut = match text.strip_suffix('\r') {
+                    Some(s) => s.len(),
+                    None => text.trim_end_matches(&join).len(),
+                };
+                if text.len() - cut <= HEAD_LEN {
+                    text = &text[..cut];
+                }
+            }
+            let line_end = if open || start + valid != buf.len() {
+                ""
+            } else if text.ends_with("\r\n") {
+                "\r\n"
+            } else if text.ends_with('\n') {
+                "\n"
+            } else {
+                ""
+            };
+            let body = &text[..text.len() - line_end.len()];
+            if !body.is_empty() {
+                convert(lnum, column, body, &mut output)?;
+                column += body.chars().count();
+            }
+            output.write_all(line_end.as_bytes())?;
+            output.write_all(&rest[valid..valid + invalid])?;
+            column += invalid;
+            start += text.len() + invalid;
+            if open {
+                break;
+            }
+        }
+        buf.drain(..start);
+        if eol {
+            new_line = true;
+        }
+        if len == 0 {
+            return output.flush();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{is_numeral, parser::number};
+
+    fn convert(input: impl BufRead, keep_rest: bool) -> Vec<u8> {
+        let mut output = vec![];
+        convert_lines(input, &mut output, keep_rest, |_, head, out| {
+            let Ok((num, rest)) = number(head) else { return Ok(0) };
+            write!(out, "{num}")?;
+            Ok(head.len() - rest.len())
+        }).unwrap();
+        output
+    }
+
+    #[test]
+    fn test_convert_lines() {
+        let datas: [(&[u8], &[u8], &[u8]); 8] = [
+            (b"", b"", b""),
+            ("十二".as_bytes(), b"12", b"12"),
+            ("十二章\n".as_bytes(), "12章\n".as_bytes(), b"12\n"),
+            ("十二\r\n三\n".as_bytes(), b"12\r\n3\n", b"12\r\n3\n"),
+            ("章\n十\n".as_bytes(), "章\n10\n".as_bytes(), b"\n10\n"),
+            (b"\n\n", b"\n\n", b"\n\n"),
+            (b"\xff\xfe\n", b"\xff\xfe\n", b"\n"),
+            (b"\xe5\x8d\x81\xff\xe4\xb8\x89\n", b"10\xff\xe4\xb8\x89\n", b"10\n"),
+        ];
+        for (input, kept, discarded) in datas {
+            assert_eq!(convert(input, true), kept, "{input:?}");
+            assert_eq!(convert(input, false), discarded, "{input:?}");
+        }
+    }
+
+    #[test]
+    fn test_over_consumed() {
+        let mut output = vec![];
+        let err = convert_lines("十二\n".as_bytes(), &mut output, true, |_, head, _| {
+            Ok(head.len() + 1)
+        }).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn test_long_line() {
+        let long = "章".repeat(HEAD_LEN);
+        for eol in ["", "\n", "\r\n"] {
+            let input = format!("十二{long}{eol}三{eol}");
+            let (kept, discarded) = match eol {
+                // a single line
+                "" => (format!("12{long}三"), String::from("12")),
+                eol => (format!("12{long}{eol}3{eol}"), format!("12{eol}3{eol}")),
+            };
+            // small buffers split the line ending
+            for cap in [1, 2, 7, 8192] {
+                let reader = || io::BufReader::with_capacity(cap, input.as_bytes());
+                assert_eq!(convert(reader(), true), kept.as_bytes(), "{eol:?}");
+                assert_eq!(convert(reader(), false), discarded.as_bytes(), "{eol:?}");
+            }
+        }
+    }
+
+    /// Line number, column and text of a segment
+    type Segment<'a> = (u64, usize, &'a str);
+
+    /// Segments of each line by [`convert_text`], and the output
+    fn segments(input: &[u8], cap: usize) -> (Vec<(u64, usize, String)>, Vec<u8>) {
+        let reader = io::BufReader::with_capacity(cap, input);
+        let mut segments: Vec<(u64, usize, String)> = vec![];
+        let mut output = vec![];
+        convert_text(reader, &mut output, is_numeral, |lnum, column, text, out| {
+            // join the segments split by the chunks
+            match segments.last_mut() {
+                Some((l, c, s)) if *l == lnum && *c + s.chars().count() == column => {
+                    // only the long runs are split
+                    let run = s.len() - s.trim_end_matches(is_numeral).len();
+                    assert!(run == 0 || run > HEAD_LEN || !text.starts_with(is_numeral));
+                    s.push_str(text);
+                },
+                _ => segments.push((lnum, column, text.into())),
+            }
+            out.write_all(text.to_uppercase().as_bytes())
+        }).unwrap();
+        (segments, output)
+    }
+
+    #[test]
+    fn test_convert_text() {
+        let datas: [(&[u8], &[Segment<'_>], &[u8]); 7] = [
+            (b"", &[], b""),
+            (b"ab\n", &[(1, 0, "ab")], b"AB\n"),
+            (b"ab\r\ncd", &[(1, 0, "ab"), (2, 0, "cd")], b"AB\r\nCD"),
+            (b"\n\nab\n", &[(3, 0, "ab")], b"\n\nAB\n"),
+            (b"ab\xffcd\n", &[(1, 0, "ab"), (1, 3, "cd")], b"AB\xffCD\n"),
+            (
+                b"a\xe5\x8d\x81\xff\xfe\xe4\xba\x8cb\n\xe4\xba",
+                &[(1, 0, "a十"), (1, 4, "二b")],
+                b"A\xe5\x8d\x81\xff\xfe\xe4\xba\x8cB\n\xe4\xba",
+            ),
+            (b"\xff\n", &[], b"\xff\n"),
+        ];
+        for (input, expected, output) in datas {
+            for cap in [1, 2, 3, 7, 8192] {
+                let (segs, out) = segments(input, cap);
+                let segs = segs.iter()
+                    .map(|(l, c, s)| (*l, *c, s.as_str()))
+                    .collect::<Vec<_>>();
+                assert_eq!(segs, expected, "{input:02X?} {cap}");
+                assert_eq!(out, output, "{input:02X?} {cap}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_convert_text_long_line() {
+        let long = "章".repeat(HEAD_LEN);
+        let input = format!("{long}一十二\nx{long}一十二\r\n");
+        for cap in [1, 7, 8192] {
+            let (segs, out) = segments(input.as_bytes(), cap);
+            assert!(out == input.to_uppercase().as_bytes(), "{cap}");
+            let lines = segs.iter()
+                .map(|(l, c, s)| (*l, *c, s.ends_with("一十二")))
+                .collect::<Vec<_>>();
+            assert_eq!(lines, [(1, 0, true), (2, 0, true)], "{cap}");
+        }
+        // a long run of numeral chars is split
+        let input = "十".repeat(HEAD_LEN * 2);
+        let (segs, out) = segments(input.as_bytes(), 8192);
+        assert!(out == input.as_bytes());
+        assert!(segs == [(1, 0, input.clone())]);
+    }
+}
@@ -0,0 +1,156 @@
+use std::{
+    error::Error,
+    fmt::{self, Display},
+};
+
+use crate::{fast, is_numeral, Number};
+
+/// Kind of [`UpperError`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum UpperErrorKind {
+    /// A numeral char not allowed, e.g `二` `两` `〇` `5`
+    NotUpper(char),
+    /// The char expected by the canonical spelling, e.g `零` of `壹万捌拾陆`
+    Expected(char),
+    /// Extra chars after the canonical spelling, e.g `壹拾零`
+    ExpectedEnd,
+    /// No number at the start, e.g `佰` `元`
+    NotNumber,
+    /// Out of range of [`Number`]
+    OutOfRange,
+}
+impl Display for UpperErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotUpper(ch) => write!(f, "unexpected `{ch}`, expected upper numerals"),
+            Self::Expected(ch) => write!(f, "expected `{ch}`"),
+            Self::ExpectedEnd => f.write_str("expected end of number"),
+            Self::NotNumber => f.write_str("expected upper number"),
+            Self::OutOfRange => f.write_str("number out of range"),
+        }
+    }
+}
+
+/// Error of [`upper_number`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct UpperError {
+    /// Byte offset of the offending char
+    pub offset: usize,
+    /// Column of the offending char in chars, starts from 1
+    pub column: usize,
+    pub kind: UpperErrorKind,
+}
+impl Display for UpperError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "error at {}: {}", self.column, self.kind)
+    }
+}
+impl Error for UpperError {}
+
+fn is_upper(ch: char) -> bool {
+    matches!(ch,
+        '零' | '壹' | '贰' | '叁' | '肆' | '伍' | '陆' | '柒' | '捌' | '玖'
+        | '拾' | '佰' | '仟' | '万' | '亿')
+}
+
+/// Parse financial upper zh nums strictly, return parsed number and rest text
+///
+/// Only `零壹贰叁肆伍陆柒捌玖拾佰仟万亿` are allowed,
+/// and the number must be spelled as [`to_zh_num_upper`],
+/// e.g the mandatory `零` and the leading `壹` of `壹拾`.
+/// The error is at the first offending char,
+/// other numeral chars are reported before the spelling
+///
+/// [`to_zh_num_upper`]: crate::to_zh_num_upper
+///
+/// # Examples
+/// ```
+/// # use zh_num::{upper_number, UpperErrorKind};
+/// assert_eq!(upper_number("壹万零捌拾陆元整"), Ok((10086, "元整")));
+/// assert_eq!(upper_number("壹拾元"), Ok((10, "元")));
+///
+/// let err = upper_number("壹万捌拾陆").unwrap_err();
+/// assert_eq!(err.offset, "壹万".len());
+/// assert_eq!(err.kind, UpperErrorKind::Expected('零'));
+/// assert_eq!(err.to_string(), "error at 3: expected `零`");
+///
+/// let err = upper_number("壹千二百").unwrap_err();
+/// assert_eq!(err.kind, UpperErrorKind::NotUpper('千'));
+/// assert!(upper_number("拾").is_err());
+/// assert!(upper_number("两佰").is_err());
+/// ```
+pub fn upper_number(s: &str) -> Result<(Number, &str), UpperError> {
+    let len = s.find(|ch| !is_upper(ch)).unwrap_or(s.len());
+    let (text, rest) = s.split_at(len);
+    let error = |offset, kind| {
+        let column = s[..offset].chars().count() + 1;
+        Err(UpperError { offset, column, kind })
+    };
+    if let Some(ch) = rest.chars().next().filter(|&ch| is_numeral(ch)) {
+        return error(len, UpperErrorKind::NotUpper(ch));
+    }
+    let num = match fast::number(text) {
+        Some((num, _)) => num,
+        // a digit always starts a number, so only overflow fails
+        None if text.starts_with(|ch| !matches!(ch, '拾' | '佰' | '仟' | '万' | '亿')) => {
+            return error(0, UpperErrorKind::OutOfRange);
+        },
+        None => return error(0, UpperErrorKind::NotNumber),
+    };
+    let canonical = fast::ZhBuf::new_upper(num);
+    let mut expected = canonical.as_str().chars();
+    for (i, ch) in text.char_indices() {
+        match expected.next() {
+            Some(exp) if exp == ch => (),
+            Some(exp) => return error(i, UpperErrorKind::Expected(exp)),
+            None => return error(i, UpperErrorKind::ExpectedEnd),
+        }
+    }
+    match expected.next() {
+        Some(exp) => error(len, UpperErrorKind::Expected(exp)),
+        None => Ok((num, rest)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::to_zh_num_upper;
+    use UpperErrorKind::*;
+
+    #[test]
+    fn test_upper_number() {
+        (0..150)
+            .chain((150..10000000).step_by(293))
+            .chain([1_0000_0000, 1_0000_0001, 1_0001_0000, u64::MAX])
+            .for_each(|n| {
+                let s = format!("{}元整", to_zh_num_upper(n));
+                assert_eq!(upper_number(&s), Ok((n, "元整")));
+            });
+        let datas = [
+            ("壹万捌拾陆", 2, Expected('零')),
+            ("壹佰伍", 2, Expected('零')),
+            ("壹佰零零伍", 3, Expected('伍')),
+            ("拾伍", 0, Expected('壹')),
+            ("壹拾零", 2, ExpectedEnd),
+            ("零壹", 0, Expected('壹')),
+            ("壹千二百", 1, NotUpper('千')),
+            ("两佰", 0, NotUpper('两')),
+            ("壹佰〇伍", 2, NotUpper('〇')),
+            ("幺佰", 0, NotUpper('幺')),
+            ("壹陌", 1, NotUpper('陌')),
+            ("伍佰5", 2, NotUpper('5')),
+            ("佰", 0, NotNumber),
+            ("元", 0, NotNumber),
+            ("", 0, NotNumber),
+            ("玖仟玖佰亿亿", 0, OutOfRange),
+        ];
+        for (src, chars, kind) in datas {
+            let err = upper_number(src).unwrap_err();
+            assert_eq!(err.column - 1, chars, "{src}");
+            let offset = src.char_indices().nth(chars).map_or(src.len(), |(i, _)| i);
+            assert_eq!(err.offset, offset, "{src}");
+            assert_eq!(err.kind, kind, "{src}");
+        }
+    }
+}